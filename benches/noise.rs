@@ -0,0 +1,127 @@
+//! Benchmarks of the building blocks, every iteration evaluates 1000 positions.
+//!
+//! Run them with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use noise::blocks::*;
+use noise::hash::{PermutationTable, PcgHash};
+use noise::interpolate::{CosInterpolator, LinearInterpolator, PerlinInterpolator};
+use noise::{DefaultI32Noise, Gradients, InterpolatedNoise2D, Noise, SimplexNoise2D, WorleyDistance,
+            WorleyReturn};
+use std::hint::black_box;
+
+fn sample_1d<N: Noise<f64, Out=f64>>(noise: &N) {
+    let mut f = 0.0;
+    for _ in 0..1000 {
+        black_box(noise.value(f));
+        f += 0.33333;
+    }
+}
+
+fn sample_2d<N: Noise<(f64, f64), Out=f64>>(noise: &N, freq: f64) {
+    let mut f = 0.0;
+    let mut g = 0.0;
+    for _ in 0..1000 {
+        black_box(noise.value((f, g)));
+        f += 0.1343 * freq;
+        g += 0.5644 * freq;
+    }
+}
+
+fn sample_3d<N: Noise<(f64, f64, f64), Out=f64>>(noise: &N) {
+    let mut f = 0.0;
+    let mut g = 0.0;
+    let mut h = 0.0;
+    for _ in 0..1000 {
+        black_box(noise.value((f, g, h)));
+        f += 0.1343;
+        g += 0.5644;
+        h += 0.3217;
+    }
+}
+
+fn sample_4d<N: Noise<(f64, f64, f64, f64), Out=f64>>(noise: &N) {
+    let mut f = 0.0;
+    let mut g = 0.0;
+    let mut h = 0.0;
+    let mut e = 0.0;
+    for _ in 0..1000 {
+        black_box(noise.value((f, g, h, e)));
+        f += 0.1343;
+        g += 0.5644;
+        h += 0.3217;
+        e += 0.2851;
+    }
+}
+
+fn noise_1d_benches(c: &mut Criterion) {
+    c.bench_function("noise_1d_linear_1000values", |b| b.iter(|| {
+        sample_1d(&new_noise_1d_int(0, 1.0, 0.05, Flavor::Value, LinearInterpolator));
+    }));
+    c.bench_function("noise_1d_perlin_1000values", |b| b.iter(|| {
+        sample_1d(&new_noise_1d_int(0, 1.0, 0.05, Flavor::Value, PerlinInterpolator));
+    }));
+    c.bench_function("noise_1d_cos_1000values", |b| b.iter(|| {
+        sample_1d(&new_noise_1d_int(0, 1.0, 0.05, Flavor::Value, CosInterpolator));
+    }));
+    c.bench_function("gradient_noise_1d_1000values", |b| b.iter(|| {
+        sample_1d(&new_noise_1d(0, 1.0, 0.05, Flavor::Gradient));
+    }));
+}
+
+fn gradient_noise_benches(c: &mut Criterion) {
+    c.bench_function("noise_2d_1000values", |b| b.iter(|| sample_2d(&new_noise_2d(0, 1.0, 0.05), 1.0)));
+    let noise = new_noise_2d(0, 1.0, 0.05);
+    c.bench_function("noise_2d_prebuilt_1000values", |b| b.iter(|| sample_2d(&noise, 1.0)));
+    c.bench_function("noise_2d_legacy_1000values", |b| b.iter(|| {
+        sample_2d(&InterpolatedNoise2D::with_gradients(DefaultI32Noise::new(0), PerlinInterpolator,
+                                                       Gradients::Legacy), 0.05);
+    }));
+    c.bench_function("noise_3d_1000values", |b| b.iter(|| sample_3d(&new_noise_3d(0, 1.0, 0.05))));
+    c.bench_function("noise_4d_1000values", |b| b.iter(|| sample_4d(&new_noise_4d(0, 1.0, 0.05))));
+}
+
+fn simplex_noise_benches(c: &mut Criterion) {
+    c.bench_function("simplex_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_simplex_noise_2d(0, 1.0, 0.05), 1.0);
+    }));
+    c.bench_function("simplex_noise_2d_permutation_table_1000values", |b| b.iter(|| {
+        sample_2d(&SimplexNoise2D::new(PermutationTable::new(0)), 0.05);
+    }));
+    c.bench_function("simplex_noise_2d_pcg_hash_1000values", |b| b.iter(|| {
+        sample_2d(&SimplexNoise2D::new(PcgHash::new(0)), 0.05);
+    }));
+    c.bench_function("simplex_noise_3d_1000values", |b| b.iter(|| {
+        sample_3d(&new_simplex_noise_3d(0, 1.0, 0.05));
+    }));
+    c.bench_function("simplex_noise_4d_1000values", |b| b.iter(|| {
+        sample_4d(&new_simplex_noise_4d(0, 1.0, 0.05));
+    }));
+    c.bench_function("open_simplex_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_open_simplex_noise_2d(0, 1.0, 0.05), 1.0);
+    }));
+    c.bench_function("open_simplex_noise_3d_1000values", |b| b.iter(|| {
+        sample_3d(&new_open_simplex_noise_3d(0, 1.0, 0.05));
+    }));
+    c.bench_function("open_simplex_noise_4d_1000values", |b| b.iter(|| {
+        sample_4d(&new_open_simplex_noise_4d(0, 1.0, 0.05));
+    }));
+}
+
+fn other_noise_benches(c: &mut Criterion) {
+    c.bench_function("worley_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_worley_noise_2d(0, 1.0, 0.05, WorleyDistance::Euclidean, WorleyReturn::F1), 1.0);
+    }));
+    c.bench_function("value_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_value_noise_2d(0, 1.0, 0.05), 1.0);
+    }));
+    c.bench_function("ridged_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_ridged_noise_2d(0, 1.0, 0.05, 6), 1.0);
+    }));
+    c.bench_function("billow_noise_2d_1000values", |b| b.iter(|| {
+        sample_2d(&new_billow_noise_2d(0, 1.0, 0.05, 6), 1.0);
+    }));
+}
+
+criterion_group!(benches, noise_1d_benches, gradient_noise_benches, simplex_noise_benches, other_noise_benches);
+criterion_main!(benches);
@@ -1,26 +1,20 @@
-#![feature(old_path, core)]
-extern crate image;
-extern crate noise;
-extern crate rand;
-
-use image::{GenericImage, ImageBuffer, Rgb};
+use image::{ImageBuffer, Rgb};
 use noise::Noise;
-use noise::blocks::new_noise_1d_int;
+use noise::blocks::{new_noise_1d_int, Flavor};
 use noise::interpolate::{CosInterpolator, LinearInterpolator, PerlinInterpolator};
-use std::old_path::Path;
 
 fn main() {
     let seed = rand::random();
     let amp = 60.0;
     let freq = 0.02;
 
-    let noise_a = new_noise_1d_int(seed, amp, freq, CosInterpolator);
-    let noise_b = new_noise_1d_int(seed, amp, freq, PerlinInterpolator);
-    let noise_c = new_noise_1d_int(seed, amp, freq, LinearInterpolator);
+    let noise_a = new_noise_1d_int(seed, amp, freq, Flavor::Value, CosInterpolator);
+    let noise_b = new_noise_1d_int(seed, amp, freq, Flavor::Value, PerlinInterpolator);
+    let noise_c = new_noise_1d_int(seed, amp, freq, Flavor::Value, LinearInterpolator);
 
-    let mut image = ImageBuffer::from_pixel(512, amp as u32 * 3 + 30, Rgb([255, 255, 255]));
+    let mut image = ImageBuffer::from_pixel(512, amp as u32 * 3 + 30, Rgb([255u8, 255, 255]));
 
-    for x in range(0, 512) {
+    for x in 0..512 {
         assert!(x < image.width());
 
         let a = (noise_a.value(x as f64) + 75.0) as u32;
@@ -35,8 +29,7 @@ fn main() {
         image.put_pixel(x, c, Rgb([0, 0, 255]));
     }
 
-    match image.save(&Path::new("output.png")) {
-        Err(e) => panic!("Could not write file! {}", e),
-        Ok(..) => {},
-    }; 
+    if let Err(e) = image.save("output.png") {
+        panic!("Could not write file! {}", e);
+    }
 }
@@ -1,12 +1,6 @@
-#![feature(old_path)]
-extern crate image;
-extern crate noise;
-extern crate rand;
-
 use image::{ImageBuffer, Rgb};
 use noise::Noise;
 use noise::blocks::new_noise_2d;
-use std::num::Float;
 
 fn main() {
     let amp = 76.5;
@@ -18,9 +12,8 @@ fn main() {
         let v = (127.5 + noise.value((x as f64, y as f64))).round();
         Rgb([v as u8, v as u8, v as u8])
     });
-    match image.save(&Path::new("output.png")) {
-        Err(e) => panic!("Could not write file! {}", e),
-        Ok(..) => {},
-    };      
+    if let Err(e) = image.save("output.png") {
+        panic!("Could not write file! {}", e);
+    }
 }
 
@@ -1,12 +1,6 @@
-#![feature(old_path)]
-extern crate image;
-extern crate noise;
-extern crate rand;
-
 use image::{ImageBuffer, Rgb};
 use noise::Noise;
 use noise::blocks::new_perlin_noise_2d;
-use std::num::Float;
 
 fn to_color(value: f64, factor: f64) -> u8 {
     let mut v = value.abs();
@@ -32,9 +26,8 @@ fn main() {
             to_color(noise_b.value(p), 1.0)
         ])
     });
-    match image.save(&Path::new("output.png")) {
-        Err(e) => panic!("Could not write file! {}", e),
-        Ok(..) => {},
-    };
+    if let Err(e) = image.save("output.png") {
+        panic!("Could not write file! {}", e);
+    }
 }
 
@@ -1,10 +1,5 @@
-#![feature(old_path)]
-extern crate image;
-extern crate noise;
-extern crate rand;
-
 use image::{ImageBuffer, Rgb};
-use noise::{Noise, OutputOp, UnboxNoise};
+use noise::{Noise, NoiseExt};
 use noise::blocks::new_perlin_noise_2d;
 
 fn map(value: f64, from_a: f64, from_b: f64, to_a: u8, to_b: u8) -> u8 {
@@ -19,9 +14,8 @@ fn main() {
     let amp = 1.0;
     let f = 0.01;
 
-    let noise = OutputOp::new(
-        UnboxNoise::new(new_perlin_noise_2d(rand::random(), amp, f, 6)),
-        |h: f64| {
+    let noise = new_perlin_noise_2d(rand::random(), amp, f, 6)
+        .map(|h: f64| {
             if h < 0.0 {
                 (0, 0, map(h, -1.0, 0.0, 100, 200))
             } else if h < 0.05 {
@@ -35,17 +29,15 @@ fn main() {
                 let g = map(h, 0.65, 1.0, 230, 255);
                 (g, g, g)
             }
-        }
-    );
+        });
 
     let size = 128;
     let image = ImageBuffer::from_fn(size, size, |x: u32, y: u32| {
         let (r, g, b) = noise.value((x as f64, y as f64));
         Rgb([r, g, b])
     });
-    match image.save(&Path::new("output.png")) {
-        Err(e) => panic!("Could not write file! {}", e),
-        Ok(..) => {},
-    };
+    if let Err(e) = image.save("output.png") {
+        panic!("Could not write file! {}", e);
+    }
 }
 
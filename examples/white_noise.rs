@@ -1,8 +1,3 @@
-#![feature(old_path)]
-extern crate image;
-extern crate noise;
-extern crate rand;
-
 use image::{ImageBuffer, Rgb};
 use noise::Noise;
 use noise::blocks::new_white_noise;
@@ -15,9 +10,8 @@ fn main() {
         let v = noise.value((x * size + y) as i32) as u8;
         Rgb([v, v, v])
     });
-    match image.save(&Path::new("output.png")) {
-        Err(e) => panic!("Could not write file! {}", e),
-        Ok(..) => {},
-    };
+    if let Err(e) = image.save("output.png") {
+        panic!("Could not write file! {}", e);
+    }
 }
 
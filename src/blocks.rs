@@ -2,55 +2,103 @@
 //! the primitive noise blocks.
 //!
 //! Just create the noise type you need and start using it! If you have
-//! to make adjustments you can extend the returned values using `UnboxNoise::new`
-//! and then combine it with all building blocks available. (This is subject to
-//! change, once `-> impl ...` return types have landed in Rust.)
+//! to make adjustments you can pass the returned values to all building blocks
+//! available, everything is statically dispatched. Use `Box<dyn Noise<...>>`
+//! only if you have to store different kinds of noise in the same place, and
+//! `Rc` or references to share one noise between several building blocks.
+//!
+//! All functions work with `f32` as well as `f64` positions and results.
 //!
 //! Take a look at the source of this module if you want to build
 //! your own noise functions.
 
-use noise::Noise;
-use input_op::InputOp;
-use output_op::OutputOp;
-use interpolated_noise::{InterpolatedNoise, InterpolatedNoise2D};
-use interpolate::{Interpolator, PerlinInterpolator};
-use default_noise::DefaultI32Noise;
-use combined_noise::CombinedNoise;
-use std::num::Float;
+use crate::noise::Noise;
+use crate::input_op::InputOp;
+use crate::output_op::OutputOp;
+use crate::interpolated_noise::{InterpolatedNoise, InterpolatedNoise2D, InterpolatedNoise3D, InterpolatedNoise4D,
+                                InterpolatedNoiseND, GradientNoise1D};
+use crate::interpolate::{Interpolator, PerlinInterpolator};
+use crate::default_noise::DefaultI32Noise;
+use crate::fractal::{Fractal, Fbm, RidgedMulti, Billow, Turbulence};
+use crate::simplex_noise::{SimplexNoise2D, SimplexNoise3D, SimplexNoise4D};
+use crate::open_simplex_noise::{OpenSimplexNoise2D, OpenSimplexNoise3D, OpenSimplexNoise4D};
+use crate::value_noise::{ValueNoise2D, ValueNoise3D};
+use crate::worley_noise::{WorleyNoise2D, WorleyNoise3D, WorleyCell, WorleyDistance, WorleyReturn};
+use crate::displace::{Displace1D, Displace2D, Displace3D};
+use num_traits::Float;
+use crate::float::{cast, to_f64};
+
+/// The kind of one-dimensional noise generated by `new_noise_1d` and `new_perlin_noise_1d`.
+#[derive(Clone, Copy)]
+pub enum Flavor {
+    /// Value noise (`InterpolatedNoise`), the source noise is interpolated directly.
+    Value,
+    /// Gradient noise (`GradientNoise1D`), this has the same spectrum as the two-dimensional
+    /// noise.
+    Gradient,
+}
+
+// The noise of either flavor, this way both share one return type.
+enum FlavoredNoise<V, G> {
+    Value(V),
+    Gradient(G),
+}
+
+impl<In, Out, V: Noise<In, Out=Out>, G: Noise<In, Out=Out>> Noise<In> for FlavoredNoise<V, G> {
+    type Out = Out;
 
-/// Generates value-interpolated one-dimensional continuous noise.
+    fn value(&self, position: In) -> Out {
+        match *self {
+            FlavoredNoise::Value(ref noise) => noise.value(position),
+            FlavoredNoise::Gradient(ref noise) => noise.value(position),
+        }
+    }
+}
+
+/// Generates interpolated one-dimensional continuous noise.
 ///
 /// # Parameters
 ///
 /// * `seed` is the seed used for the underlying random number generator.
 /// * `amp` is the amplitude of the resulting noise (values will be from `-amp` to `+amp`).
 /// * `freq` is the frequency of the noise.
+/// * `flavor` selects between value and gradient noise.
 /// * `interpolator` is the interpolator that will be used to interpolate.
-pub fn new_noise_1d_int<'a, I: Interpolator<f64> + 'a>(seed: i32, amp: f64, freq: f64, interpolator: I)
-        -> Box<Noise<f64, Out=f64> + 'a> {  // TODO use impl Noise<...>
-    Box::new(
-        OutputOp::new(
-            InputOp::new(
-                InterpolatedNoise::new(
-                    DefaultI32Noise::new(seed),
-                    interpolator
-                ),
-                move |p: f64| { p * freq }
-            ),
-            move |f: f64| { f * amp }
-        )
+pub fn new_noise_1d_int<F: Float, I: Interpolator<F>>(seed: i32, amp: F, freq: F, flavor: Flavor, interpolator: I)
+        -> OutputOp<impl Noise<F, Out=F>, impl Fn(F) -> F> {
+    let noise = match flavor {
+        Flavor::Value => FlavoredNoise::Value(
+            InterpolatedNoise::<_, _, i64>::new(
+                OutputOp::new(DefaultI32Noise::new(seed), |v: f64| { cast::<F>(v) }),
+                interpolator
+            )
+        ),
+        Flavor::Gradient => FlavoredNoise::Gradient(
+            GradientNoise1D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            )
+        ),
+    };
+    OutputOp::new(
+        InputOp::new(
+            noise,
+            move |p: F| { p * freq }
+        ),
+        move |f: F| { f * amp }
     )
 }
 
-/// Generates smooth (value-interpolated) one-dimensional continuous noise.
+/// Generates smooth one-dimensional continuous noise.
 ///
 /// # Parameters
 ///
 /// * `seed` is the seed used for the underlying random number generator.
 /// * `amp` is the amplitude of the resulting noise (values will be from `-amp` to `+amp`).
 /// * `freq` is the frequency of the noise.
-pub fn new_noise_1d(seed: i32, amp: f64, freq: f64) -> Box<Noise<f64, Out=f64> + 'static> {  // TODO use impl
-	new_noise_1d_int(seed, amp, freq, PerlinInterpolator)
+/// * `flavor` selects between value and gradient noise.
+pub fn new_noise_1d<F: Float>(seed: i32, amp: F, freq: F, flavor: Flavor) -> OutputOp<impl Noise<F, Out=F>, impl Fn(F) -> F> {
+	new_noise_1d_int(seed, amp, freq, flavor, PerlinInterpolator)
 }
 
 /// Generates two-dimensional continuous gradient noise.
@@ -61,132 +109,557 @@ pub fn new_noise_1d(seed: i32, amp: f64, freq: f64) -> Box<Noise<f64, Out=f64> +
 /// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
 /// * `freq_x` and `freq_y` is the requency of the noise.
 /// * `interpolator` is the interpolator to be used.
-// TODO use impl
 pub fn new_noise_2d_ex
-        <'a, I: Interpolator<f64> + 'a>
-        (seed: i32, amp: f64, (freq_x, freq_y): (f64, f64), interpolator: I)
-        -> Box<Noise<(f64, f64), Out=f64> + 'a> {
-    Box::new(
-        OutputOp::new(
-            InputOp::new(
-                InterpolatedNoise2D::new(
-                    DefaultI32Noise::new(seed),
-                    interpolator
-                ),
-                move |(x, y): (f64, f64)| { (x * freq_x, y * freq_y) }
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, (freq_x, freq_y): (F, F), interpolator: I)
+        -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            InterpolatedNoise2D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
             ),
-            move |f: f64| { f * amp }
-        )
+            move |(x, y): (F, F)| { (x * freq_x, y * freq_y) }
+        ),
+        move |f: F| { f * amp }
     )
 }
 
 /// Generates two dimensional gradient noise using sensible defaults.
-// TODO use impl
-pub fn new_noise_2d(seed: i32, amp: f64, freq: f64) -> Box<Noise<(f64, f64), Out=f64> + 'static> {
+pub fn new_noise_2d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
     new_noise_2d_ex(seed, amp, (freq, freq), PerlinInterpolator)
 }
 
+/// Generates three-dimensional continuous gradient noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq_x`, `freq_y` and `freq_z` is the frequency of the noise.
+/// * `interpolator` is the interpolator to be used.
+pub fn new_noise_3d_ex
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, (freq_x, freq_y, freq_z): (F, F, F), interpolator: I)
+        -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            InterpolatedNoise3D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            ),
+            move |(x, y, z): (F, F, F)| { (x * freq_x, y * freq_y, z * freq_z) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates three dimensional gradient noise using sensible defaults.
+pub fn new_noise_3d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    new_noise_3d_ex(seed, amp, (freq, freq, freq), PerlinInterpolator)
+}
+
+/// Generates four-dimensional continuous gradient noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq_x`, `freq_y`, `freq_z` and `freq_w` is the frequency of the noise.
+/// * `interpolator` is the interpolator to be used.
+pub fn new_noise_4d_ex
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, (freq_x, freq_y, freq_z, freq_w): (F, F, F, F), interpolator: I)
+        -> OutputOp<impl Noise<(F, F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            InterpolatedNoise4D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            ),
+            move |(x, y, z, w): (F, F, F, F)| { (x * freq_x, y * freq_y, z * freq_z, w * freq_w) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates four dimensional gradient noise using sensible defaults.
+pub fn new_noise_4d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F, F), Out=F>, impl Fn(F) -> F> {
+    new_noise_4d_ex(seed, amp, (freq, freq, freq, freq), PerlinInterpolator)
+}
+
+/// Generates continuous gradient noise, the number of dimensions is given by the length of the
+/// position.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise.
+/// * `freq` is the frequency of the noise along all axes.
+/// * `interpolator` is the interpolator to be used.
+pub fn new_noise_nd_ex
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, freq: F, interpolator: I)
+        -> OutputOp<impl Noise<Vec<F>, Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            InterpolatedNoiseND::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            ),
+            move |p: Vec<F>| { p.into_iter().map(|x| x * freq).collect::<Vec<F>>() }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates gradient noise of runtime dimension using sensible defaults.
+pub fn new_noise_nd<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<Vec<F>, Out=F>, impl Fn(F) -> F> {
+    new_noise_nd_ex(seed, amp, freq, PerlinInterpolator)
+}
+
 /// Generates coherent one-dimensional so called Perlin Noise.
 ///
 /// This noise is generated by adding `noise_1d(amp, freq)`,
 /// `noise_1d(amp / 2, freq * 2)`, `noise_1d(amp / 4, freq * 4)`
-/// and so on (octaves many times).
-// TODO use impl
-pub fn new_perlin_noise_1d(seed: i32, amp: f64, freq: f64, octaves: usize) -> Box<Noise<f64, Out=f64> + 'static> {
-	let mut noises = Vec::with_capacity(octaves);
+/// and so on (octaves many times). `flavor` selects the kind of noise
+/// used for the octaves.
+pub fn new_perlin_noise_1d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize, flavor: Flavor) -> Fbm<impl Noise<F, Out=F>> {
+	Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+		new_noise_1d(seed, F::one(), cast(freq), flavor)
+	})
+}
+
+/// Generates coherent two-dimensional Perlin Noise.
+pub fn new_perlin_noise_2d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_2d(seed, F::one(), cast(freq))
+    })
+}
 
-	let mut factor = 1.0;
-	let mut current_seed = seed;
-	for _ in 0..octaves {
-		noises.push(new_noise_1d(current_seed, amp / factor, freq * factor));
-		factor *= 2.0;
-		current_seed *= seed;
-	}
+/// Generates coherent three-dimensional Perlin Noise.
+pub fn new_perlin_noise_3d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_3d(seed, F::one(), cast(freq))
+    })
+}
 
-	Box::new(CombinedNoise::new(noises, |a: f64, b: f64| { a + b }))
+/// Generates coherent four-dimensional Perlin Noise.
+pub fn new_perlin_noise_4d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F, F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_4d(seed, F::one(), cast(freq))
+    })
 }
 
-/// Generates coherent two-dimensional Perlin Noise.
-// TODO use impl
-pub fn new_perlin_noise_2d(seed: i32, amp: f64, freq: f64, octaves: usize) -> Box<Noise<(f64, f64), Out=f64> + 'static> {
-    let mut noises = Vec::with_capacity(octaves);
-
-    let mut factor = 1.0;
-    let mut current_seed = seed;
-    for _ in 0..octaves {
-        noises.push(new_noise_2d(current_seed, amp / factor, freq * factor));
-        factor *= 2.0;
-        current_seed *= seed;
-    }
+/// Generates coherent Perlin Noise of runtime dimension.
+pub fn new_perlin_noise_nd<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<Vec<F>, Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_nd(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates one-dimensional ridged multifractal noise from gradient noise.
+///
+/// The octaves are combined using `RidgedMulti` with an offset of 1 and a gain of 2, the result
+/// is between `-amp` and about `1.5 * amp`.
+pub fn new_ridged_noise_1d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> RidgedMulti<impl Noise<F, Out=F>> {
+    RidgedMulti::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), 1.0, 2.0, |seed, freq| {
+        new_noise_1d(seed, F::one(), cast(freq), Flavor::Gradient)
+    })
+}
+
+/// Generates two-dimensional ridged multifractal noise, well suited for mountain ranges.
+///
+/// See `new_ridged_noise_1d` for details.
+pub fn new_ridged_noise_2d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> RidgedMulti<impl Noise<(F, F), Out=F>> {
+    RidgedMulti::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), 1.0, 2.0, |seed, freq| {
+        new_noise_2d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates three-dimensional ridged multifractal noise.
+///
+/// See `new_ridged_noise_1d` for details.
+pub fn new_ridged_noise_3d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> RidgedMulti<impl Noise<(F, F, F), Out=F>> {
+    RidgedMulti::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), 1.0, 2.0, |seed, freq| {
+        new_noise_3d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates one-dimensional billowy noise from gradient noise.
+///
+/// The octaves are combined using `Billow`, `amp` is the amplitude of the first octave.
+pub fn new_billow_noise_1d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Billow<impl Noise<F, Out=F>> {
+    Billow::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_1d(seed, F::one(), cast(freq), Flavor::Gradient)
+    })
+}
+
+/// Generates two-dimensional billowy noise, well suited for clouds.
+///
+/// See `new_billow_noise_1d` for details.
+pub fn new_billow_noise_2d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Billow<impl Noise<(F, F), Out=F>> {
+    Billow::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_2d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates three-dimensional billowy noise.
+///
+/// See `new_billow_noise_1d` for details.
+pub fn new_billow_noise_3d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Billow<impl Noise<(F, F, F), Out=F>> {
+    Billow::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_3d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates one-dimensional turbulence from gradient noise.
+///
+/// The octaves are combined using `Turbulence`, the result is never negative and `amp` is the
+/// amplitude of the first octave.
+pub fn new_turbulence_noise_1d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Turbulence<impl Noise<F, Out=F>> {
+    Turbulence::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_1d(seed, F::one(), cast(freq), Flavor::Gradient)
+    })
+}
+
+/// Generates two-dimensional turbulence.
+///
+/// See `new_turbulence_noise_1d` for details.
+pub fn new_turbulence_noise_2d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Turbulence<impl Noise<(F, F), Out=F>> {
+    Turbulence::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_2d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates three-dimensional turbulence.
+///
+/// See `new_turbulence_noise_1d` for details.
+pub fn new_turbulence_noise_3d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Turbulence<impl Noise<(F, F, F), Out=F>> {
+    Turbulence::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_noise_3d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates two-dimensional simplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_simplex_noise_2d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            SimplexNoise2D::new(DefaultI32Noise::new(seed)),
+            move |(x, y): (F, F)| { (x * freq, y * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates three-dimensional simplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_simplex_noise_3d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            SimplexNoise3D::new(DefaultI32Noise::new(seed)),
+            move |(x, y, z): (F, F, F)| { (x * freq, y * freq, z * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates four-dimensional simplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_simplex_noise_4d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            SimplexNoise4D::new(DefaultI32Noise::new(seed)),
+            move |(x, y, z, w): (F, F, F, F)| { (x * freq, y * freq, z * freq, w * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates two-dimensional OpenSimplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_open_simplex_noise_2d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            OpenSimplexNoise2D::new(DefaultI32Noise::new(seed)),
+            move |(x, y): (F, F)| { (x * freq, y * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates three-dimensional OpenSimplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_open_simplex_noise_3d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            OpenSimplexNoise3D::new(DefaultI32Noise::new(seed)),
+            move |(x, y, z): (F, F, F)| { (x * freq, y * freq, z * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates four-dimensional OpenSimplex noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq` is the frequency of the noise.
+pub fn new_open_simplex_noise_4d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            OpenSimplexNoise4D::new(DefaultI32Noise::new(seed)),
+            move |(x, y, z, w): (F, F, F, F)| { (x * freq, y * freq, z * freq, w * freq) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates coherent two-dimensional noise by adding octaves of simplex noise like
+/// `new_perlin_noise_2d` does.
+pub fn new_fractal_simplex_noise_2d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_simplex_noise_2d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates coherent three-dimensional noise by adding octaves of simplex noise like
+/// `new_perlin_noise_3d` does.
+pub fn new_fractal_simplex_noise_3d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_simplex_noise_3d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates coherent four-dimensional noise by adding octaves of simplex noise like
+/// `new_perlin_noise_4d` does.
+pub fn new_fractal_simplex_noise_4d<F: Float>(seed: i32, amp: F, freq: F, octaves: usize) -> Fbm<impl Noise<(F, F, F, F), Out=F>> {
+    Fbm::new(Fractal::new(seed, to_f64(amp), to_f64(freq), octaves), |seed, freq| {
+        new_simplex_noise_4d(seed, F::one(), cast(freq))
+    })
+}
+
+/// Generates two-dimensional value-interpolated noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq_x` and `freq_y` is the frequency of the noise.
+/// * `interpolator` is the interpolator to be used.
+pub fn new_value_noise_2d_ex
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, (freq_x, freq_y): (F, F), interpolator: I)
+        -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            ValueNoise2D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            ),
+            move |(x, y): (F, F)| { (x * freq_x, y * freq_y) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates two dimensional value-interpolated noise using sensible defaults.
+pub fn new_value_noise_2d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F), Out=F>, impl Fn(F) -> F> {
+    new_value_noise_2d_ex(seed, amp, (freq, freq), PerlinInterpolator)
+}
+
+/// Generates three-dimensional value-interpolated noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the amplitude of the resulting noise (values from `-amp` to `+amp`).
+/// * `freq_x`, `freq_y` and `freq_z` is the frequency of the noise.
+/// * `interpolator` is the interpolator to be used.
+pub fn new_value_noise_3d_ex
+        <F: Float, I: Interpolator<F>>
+        (seed: i32, amp: F, (freq_x, freq_y, freq_z): (F, F, F), interpolator: I)
+        -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            ValueNoise3D::new(
+                DefaultI32Noise::new(seed),
+                interpolator
+            ),
+            move |(x, y, z): (F, F, F)| { (x * freq_x, y * freq_y, z * freq_z) }
+        ),
+        move |f: F| { f * amp }
+    )
+}
+
+/// Generates three dimensional value-interpolated noise using sensible defaults.
+pub fn new_value_noise_3d<F: Float>(seed: i32, amp: F, freq: F) -> OutputOp<impl Noise<(F, F, F), Out=F>, impl Fn(F) -> F> {
+    new_value_noise_3d_ex(seed, amp, (freq, freq, freq), PerlinInterpolator)
+}
+
+/// Generates two-dimensional cellular (Worley) noise.
+///
+/// # Parameters
+///
+/// * `seed` is used to seed the underlying random number generator.
+/// * `amp` is the factor the value selected by `ret` is multiplied with.
+/// * `freq` is the frequency of the noise, i.e. the number of cells per unit.
+/// * `distance` is the function used to measure the distance to the feature points.
+/// * `ret` selects the value that is returned.
+pub fn new_worley_noise_2d<F: Float>(seed: i32, amp: F, freq: F, distance: WorleyDistance, ret: WorleyReturn)
+        -> OutputOp<impl Noise<(F, F), Out=f64>, impl Fn(f64) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            OutputOp::new(
+                WorleyNoise2D::new(DefaultI32Noise::new(seed), distance),
+                move |cell: WorleyCell<f64, (f64, f64)>| { cell.get(ret) }
+            ),
+            move |(x, y): (F, F)| { (to_f64(x * freq), to_f64(y * freq)) }
+        ),
+        move |f: f64| { cast::<F>(f) * amp }
+    )
+}
 
-    Box::new(CombinedNoise::new(noises, |a: f64, b: f64| { a + b }))
+/// Generates three-dimensional cellular (Worley) noise.
+///
+/// See `new_worley_noise_2d` for the parameters.
+pub fn new_worley_noise_3d<F: Float>(seed: i32, amp: F, freq: F, distance: WorleyDistance, ret: WorleyReturn)
+        -> OutputOp<impl Noise<(F, F, F), Out=f64>, impl Fn(f64) -> F> {
+    OutputOp::new(
+        InputOp::new(
+            OutputOp::new(
+                WorleyNoise3D::new(DefaultI32Noise::new(seed), distance),
+                move |cell: WorleyCell<f64, (f64, f64, f64)>| { cell.get(ret) }
+            ),
+            move |(x, y, z): (F, F, F)| { (to_f64(x * freq), to_f64(y * freq), to_f64(z * freq)) }
+        ),
+        move |f: f64| { cast::<F>(f) * amp }
+    )
 }
 
 /// Generates random (white) noise in the given bounds (both ends inclusive).
-// TODO use impl
-pub fn new_white_noise(seed: i32, min: f64, max: f64) -> Box<Noise<i32, Out=f64> + 'static> {
+pub fn new_white_noise<F: Float>(seed: i32, min: F, max: F) -> OutputOp<DefaultI32Noise, impl Fn(f64) -> F> {
     assert!(min <= max);
-    Box::new(
-        OutputOp::new(
-            DefaultI32Noise::new(seed),
-            move |f: f64| { f.abs() * (max - min) + min }
-        )
+    OutputOp::new(
+        DefaultI32Noise::new(seed),
+        move |f: f64| { cast::<F>(f.abs()) * (max - min) + min }
+    )
+}
+
+
+/// Displaces the positions passed to `source` by one-dimensional Perlin noise.
+///
+/// This is libnoise's turbulence, not to be confused with `new_turbulence_noise_1d`.
+///
+/// # Parameters
+///
+/// * `source` is the noise whose positions are displaced.
+/// * `seed` is used to seed the underlying random number generator.
+/// * `power` is the amplitude of the displacement.
+/// * `freq` is the frequency of the displacement.
+/// * `octaves` is the number of octaves of the displacement, more octaves give rougher results.
+pub fn new_turbulent_displace_1d<F: Float, Src: Noise<F>>(source: Src, seed: i32, power: F, freq: F, octaves: usize)
+        -> Displace1D<Src, impl Noise<F, Out=F>> {
+    Displace1D::new(source, new_perlin_noise_1d(seed, power, freq, octaves, Flavor::Gradient))
+}
+
+/// Displaces the positions passed to `source` by two-dimensional Perlin noise, every axis has
+/// its own noise.
+///
+/// See `new_turbulent_displace_1d` for the parameters.
+pub fn new_turbulent_displace_2d<F: Float, Src: Noise<(F, F)>>(source: Src, seed: i32, power: F, freq: F,
+                                                               octaves: usize)
+        -> Displace2D<Src, impl Noise<(F, F), Out=F>, impl Noise<(F, F), Out=F>> {
+    Displace2D::new(
+        source,
+        new_perlin_noise_2d(seed, power, freq, octaves),
+        new_perlin_noise_2d(seed.wrapping_add(1), power, freq, octaves)
+    )
+}
+
+/// Displaces the positions passed to `source` by three-dimensional Perlin noise, every axis has
+/// its own noise.
+///
+/// See `new_turbulent_displace_1d` for the parameters.
+#[allow(clippy::type_complexity)]
+pub fn new_turbulent_displace_3d<F: Float, Src: Noise<(F, F, F)>>(source: Src, seed: i32, power: F, freq: F,
+                                                                  octaves: usize)
+        -> Displace3D<Src, impl Noise<(F, F, F), Out=F>, impl Noise<(F, F, F), Out=F>,
+                      impl Noise<(F, F, F), Out=F>> {
+    Displace3D::new(
+        source,
+        new_perlin_noise_3d(seed, power, freq, octaves),
+        new_perlin_noise_3d(seed.wrapping_add(1), power, freq, octaves),
+        new_perlin_noise_3d(seed.wrapping_add(2), power, freq, octaves)
     )
 }
 
 #[cfg(test)]
 mod test {
-    use super::*;
-    use test::{Bencher, black_box};
-    use interpolate::{LinearInterpolator, PerlinInterpolator, CosInterpolator};
-
-    #[bench]
-    fn noise_1d_linear_bench_1000values(b: &mut Bencher) {
-        b.iter(|| {
-            let noise = new_noise_1d_int(0, 1.0, 0.05, LinearInterpolator);
-            let mut f = 0.0;
-            for _ in 0..1000 {
-                black_box(noise.value(f));
-                f += 0.33333;
-            }
-        });
-    }
+    use super::{new_noise_1d, new_noise_2d, new_perlin_noise_2d, new_turbulent_displace_2d, Flavor};
+    use crate::input_op::InputOp;
+    use crate::noise::Noise;
+    use crate::output_op::OutputOp;
 
-    #[bench]
-    fn noise_1d_perlin_bench_1000values(b: &mut Bencher) {
-        b.iter(|| {
-            let noise = new_noise_1d_int(0, 1.0, 0.05, PerlinInterpolator);
-            let mut f = 0.0;
-            for _ in 0..1000 {
-                black_box(noise.value(f));
-                f += 0.33333;
-            }
-        });
+    #[test]
+    fn compose_without_unbox_test() {
+        let noise = OutputOp::new(
+            InputOp::new(new_perlin_noise_2d(7, 1.0, 0.01, 4), |(x, y): (f64, f64)| { (y, x) }),
+            |f: f64| { f > 0.0 }
+        );
+        let perlin = new_perlin_noise_2d(7, 1.0, 0.01, 4);
+        for i in 0..100 {
+            let (x, y) = (i as f64 * 3.7, i as f64 * -1.3);
+            assert!(noise.value((x, y)) == (perlin.value((y, x)) > 0.0));
+        }
     }
 
-    #[bench]
-    fn noise_1d_cos_bench_1000values(b: &mut Bencher) {
-        b.iter(|| {
-            let noise = new_noise_1d_int(0, 1.0, 0.05, CosInterpolator);
-            let mut f = 0.0;
-            for _ in 0..1000 {
-                black_box(noise.value(f));
-                f += 0.33333;
-            }
-        });
+    #[test]
+    fn flavors_differ_test() {
+        let value = new_noise_1d(3, 1.0f32, 0.1, Flavor::Value);
+        let gradient = new_noise_1d(3, 1.0f32, 0.1, Flavor::Gradient);
+        assert!((0..100).any(|i| value.value(i as f32 * 0.77) != gradient.value(i as f32 * 0.77)));
     }
 
-    #[bench]
-    fn noise_2d_bench_1000values(b: &mut Bencher) {
-        b.iter(|| {
-            let noise = new_noise_2d(0, 1.0, 0.05);
-            let mut f = 0.0;
-            let mut g = 0.0;
-            for _ in 0..1000 {
-                black_box(noise.value((f, g)));
-                f += 0.1343;
-                g += 0.5644;
-            }
-        });
+    #[test]
+    fn turbulent_displace_test() {
+        let source = new_noise_2d(1, 1.0, 0.1);
+        let still = new_turbulent_displace_2d(new_noise_2d(1, 1.0, 0.1), 2, 0.0, 0.05, 3);
+        let warped = new_turbulent_displace_2d(new_noise_2d(1, 1.0, 0.1), 2, 4.0, 0.05, 3);
+        let mut different = false;
+        for i in 0..100 {
+            let p = (i as f64 * 1.3, i as f64 * 0.7);
+            assert!(still.value(p) == source.value(p));
+            different |= warped.value(p) != source.value(p);
+        }
+        assert!(different);
     }
 }
@@ -1,21 +1,21 @@
-use noise::Noise;
+use crate::noise::Noise;
 
 /// Creates noise by combining multiple source noises.
 ///
 /// This noise uses trait objects to manage the multiple source noises.
 pub struct CombinedNoise<'a, In, Out, Combine> {
-	sources: Vec<Box<Noise<In, Out=Out> + 'a>>,
+	sources: Vec<Box<dyn Noise<In, Out=Out> + 'a>>,
 	combine: Combine,
 }
 
 impl<'a, In, Out, Combine> CombinedNoise<'a, In, Out, Combine> {
 
-	pub fn new(sources: Vec<Box<Noise<In, Out=Out> + 'a>>, combine: Combine)
+	pub fn new(sources: Vec<Box<dyn Noise<In, Out=Out> + 'a>>, combine: Combine)
 			-> CombinedNoise<'a, In, Out, Combine> {
 		assert!(!sources.is_empty());
 		CombinedNoise{
-			sources: sources,
-			combine: combine
+			sources,
+			combine
 		}
 	}
 }
@@ -49,9 +49,9 @@ impl<Src1, Src2, Combine> CombinedNoise2<Src1, Src2, Combine> {
 	pub fn new(source1: Src1, source2: Src2, combine: Combine)
 			-> CombinedNoise2<Src1, Src2, Combine> {
 		CombinedNoise2{
-			source1: source1,
-			source2: source2,
-			combine: combine
+			source1,
+			source2,
+			combine
 		}
 	}
 }
@@ -72,8 +72,8 @@ impl<In: Clone, Out, Src1Out, Src2Out, Src1: Noise<In, Out=Src1Out>, Src2: Noise
 #[cfg(test)]
 mod test {
 	use super::{CombinedNoise, CombinedNoise2};
-	use default_noise::{NoOpNoise, ConstantNoise};
-	use noise::Noise;
+	use crate::default_noise::{NoOpNoise, ConstantNoise};
+	use crate::noise::Noise;
 
 	#[test]
 	fn combined_noise_test() {
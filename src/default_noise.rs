@@ -1,4 +1,6 @@
-use noise::Noise;
+use crate::noise::Noise;
+use crate::hash::LatticeHash;
+use crate::lattice::fold;
 use std::clone::Clone;
 
 static PRIME_POSITION: i32 = 999961;
@@ -7,7 +9,8 @@ static PRIME_SEED: i32 = 748361;
 /// Generator for pseudo-random integer noise.
 ///
 /// This is implemented using `libnoise`'s integer noise function. See the [libnoise documentation](
-/// http://libnoise.sourceforge.net/noisegen/#coherentnoise) for details.
+/// http://libnoise.sourceforge.net/noisegen/#coherentnoise) for details. It is also the default
+/// `LatticeHash` of all lattice based generators.
 pub struct DefaultI32Noise {
     seed: i32,
 }
@@ -15,19 +18,43 @@ pub struct DefaultI32Noise {
 impl DefaultI32Noise {
     pub fn new(seed: i32) -> DefaultI32Noise {
         DefaultI32Noise{
-            seed: (seed * PRIME_SEED) ^ (seed << 13),
+            seed: seed.wrapping_mul(PRIME_SEED) ^ (seed << 13),
         }
     }
+
+    /// Returns the raw 31 bit hash of the position.
+    fn integer(&self, position: i32) -> i32 {
+        // all arithmetic is explicitly wrapping, overflows are part of the hash function
+        let a = position.wrapping_mul(PRIME_POSITION).wrapping_add(self.seed);
+        let b = (a << 13) ^ a;
+        b.wrapping_mul(b.wrapping_mul(b).wrapping_mul(60493).wrapping_add(19990303))
+         .wrapping_add(1376312589) & 0x7fffffff
+    }
 }
 
 impl Noise<i32> for DefaultI32Noise {
     type Out = f64;
 
     fn value(&self, position: i32) -> f64 {
-        let a = position * PRIME_POSITION + self.seed;
-        let b = (a << 13) ^ a;
-        let c = (b * (b * b * 60493 + 19990303) + 1376312589) & 0x7fffffff;
-        1.0 - (c as f64) / 1073741824.0
+        1.0 - (self.integer(position) as f64) / 1073741824.0
+    }
+}
+
+impl Noise<i64> for DefaultI32Noise {
+    type Out = f64;
+
+    /// Folds the position to 32 bits, positions in the range of `i32` give the same value as
+    /// `Noise<i32>`.
+    fn value(&self, position: i64) -> f64 {
+        self.value(fold(position))
+    }
+}
+
+impl LatticeHash for DefaultI32Noise {
+    fn hash1(&self, x: i64) -> u32 {
+        // shifted to the top bits, this way the generators get exactly the same values as from
+        // `value`
+        (self.integer(fold(x)) as u32) << 1
     }
 }
 
@@ -39,7 +66,7 @@ pub struct ConstantNoise<Out> {
 impl<Out: Clone> ConstantNoise<Out> {
     pub fn new(value: Out) -> ConstantNoise<Out> {
         ConstantNoise{
-            value: value
+            value
         }
     }
 }
@@ -63,16 +90,19 @@ impl<In> Noise<In> for NoOpNoise {
     }
 }
 
-/// Wrapper that can be used to use `Box<Noise<...>>` as source to other
+/// Wrapper that can be used to use `Box<dyn Noise<...>>` as source to other
 /// building blocks.
+///
+/// Boxes implement `Noise` themselves, so this wrapper is only kept for
+/// compatibility.
 pub struct UnboxNoise<'a, In, Out> {
-    source: Box<Noise<In, Out=Out> + 'a>
+    source: Box<dyn Noise<In, Out=Out> + 'a>
 }
 
 impl<'a, In, Out> UnboxNoise<'a, In, Out> {
-    pub fn new(source: Box<Noise<In, Out=Out> + 'a>) -> UnboxNoise<'a, In, Out> {
+    pub fn new(source: Box<dyn Noise<In, Out=Out> + 'a>) -> UnboxNoise<'a, In, Out> {
         UnboxNoise{
-            source: source
+            source
         }
     }
 }
@@ -89,23 +119,47 @@ impl<'a, In, Out> Noise<In> for UnboxNoise<'a, In, Out> {
 #[cfg(test)]
 mod test {
     use super::{DefaultI32Noise, ConstantNoise, NoOpNoise};
-    use std::iter::range_step_inclusive;
-    use std::num::Int;
-    use noise::Noise;
+    use crate::noise::Noise;
 
     #[test]
     fn integer_noise_test() {
         let noise = DefaultI32Noise::new(0);
-        for n in range_step_inclusive(Int::min_value(), Int::max_value(), 1001) {
+        for n in (i32::MIN..=i32::MAX).step_by(1001) {
             let value = noise.value(n);
-            assert!(-1.0 <= value && value <= 1.0);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn integer_noise_extreme_test() {
+        let seeds: [i32; 5] = [0, 1, -1, i32::MIN, i32::MAX];
+        let positions: [i32; 7] = [0, 1, -1, i32::MIN, i32::MAX, 123456789, -987654321];
+        for seed in seeds.iter() {
+            let noise = DefaultI32Noise::new(*seed);
+            for position in positions.iter() {
+                let value = noise.value(*position);
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn integer_noise_i64_test() {
+        let noise = DefaultI32Noise::new(3);
+        for n in (-1000000i32..=1000000).step_by(997) {
+            assert!(noise.value(n as i64) == noise.value(n));
+        }
+        let large: [i64; 4] = [i64::MIN, i64::MAX, 1 << 40, -(1 << 50) + 7];
+        for position in large.iter() {
+            let value = noise.value(*position);
+            assert!((-1.0..=1.0).contains(&value));
         }
     }
 
     #[test]
     fn constant_noise_test() {
         let noise = ConstantNoise::new('x');
-        for n in range_step_inclusive(Int::min_value(), Int::max_value(), 1001) {
+        for n in (i32::MIN..=i32::MAX).step_by(1001) {
             let value = noise.value(n);
             assert!(value == 'x');
         }
@@ -114,7 +168,7 @@ mod test {
     #[test]
     fn no_op_noise_test() {
         let noise = NoOpNoise;
-        for n in range_step_inclusive(Int::min_value(), Int::max_value(), 1001) {
+        for n in (i32::MIN..=i32::MAX).step_by(1001) {
             let value = noise.value(n);
             assert!(value == n);
         }
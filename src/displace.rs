@@ -0,0 +1,111 @@
+use crate::noise::Noise;
+use num_traits::Float;
+
+/// Displaces the position passed to the source noise by the value of another noise.
+///
+/// The displacement noise is evaluated at the same position, the source noise at the position
+/// plus the displacement. This is also known as domain warping.
+pub struct Displace1D<Src, X> {
+    source: Src,
+    x: X,
+}
+
+impl<Src, X> Displace1D<Src, X> {
+    pub fn new(source: Src, x: X) -> Displace1D<Src, X> {
+        Displace1D{
+            source,
+            x
+        }
+    }
+}
+
+impl<F: Float, Src: Noise<F>, X: Noise<F, Out=F>> Noise<F> for Displace1D<Src, X> {
+    type Out = Src::Out;
+
+    fn value(&self, x: F) -> Src::Out {
+        self.source.value(x + self.x.value(x))
+    }
+}
+
+/// Displaces the position passed to the source noise by one noise per axis.
+///
+/// See `Displace1D`.
+pub struct Displace2D<Src, X, Y> {
+    source: Src,
+    x: X,
+    y: Y,
+}
+
+impl<Src, X, Y> Displace2D<Src, X, Y> {
+    pub fn new(source: Src, x: X, y: Y) -> Displace2D<Src, X, Y> {
+        Displace2D{
+            source,
+            x,
+            y
+        }
+    }
+}
+
+impl<F: Float, Src: Noise<(F, F)>, X: Noise<(F, F), Out=F>, Y: Noise<(F, F), Out=F>>
+        Noise<(F, F)> for Displace2D<Src, X, Y> {
+    type Out = Src::Out;
+
+    fn value(&self, (x, y): (F, F)) -> Src::Out {
+        let dx = self.x.value((x, y));
+        let dy = self.y.value((x, y));
+        self.source.value((x + dx, y + dy))
+    }
+}
+
+/// Displaces the position passed to the source noise by one noise per axis.
+///
+/// See `Displace1D`.
+pub struct Displace3D<Src, X, Y, Z> {
+    source: Src,
+    x: X,
+    y: Y,
+    z: Z,
+}
+
+impl<Src, X, Y, Z> Displace3D<Src, X, Y, Z> {
+    pub fn new(source: Src, x: X, y: Y, z: Z) -> Displace3D<Src, X, Y, Z> {
+        Displace3D{
+            source,
+            x,
+            y,
+            z
+        }
+    }
+}
+
+impl<F: Float, Src: Noise<(F, F, F)>, X: Noise<(F, F, F), Out=F>, Y: Noise<(F, F, F), Out=F>,
+     Z: Noise<(F, F, F), Out=F>> Noise<(F, F, F)> for Displace3D<Src, X, Y, Z> {
+    type Out = Src::Out;
+
+    fn value(&self, (x, y, z): (F, F, F)) -> Src::Out {
+        let dx = self.x.value((x, y, z));
+        let dy = self.y.value((x, y, z));
+        let dz = self.z.value((x, y, z));
+        self.source.value((x + dx, y + dy, z + dz))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Displace1D, Displace2D, Displace3D};
+    use crate::default_noise::{ConstantNoise, NoOpNoise};
+    use crate::input_op::InputOp;
+    use crate::noise::Noise;
+
+    #[test]
+    fn displace_test() {
+        let noise = Displace1D::new(NoOpNoise, InputOp::new(NoOpNoise, |x: f64| { x * 2.0 }));
+        assert!(noise.value(1.5) == 4.5);
+        let noise = Displace2D::new(NoOpNoise, ConstantNoise::new(1.0),
+                                    InputOp::new(NoOpNoise, |(x, _): (f64, f64)| { x }));
+        assert!(noise.value((3.0, 2.0)) == (4.0, 5.0));
+        let noise = Displace3D::new(NoOpNoise, ConstantNoise::new(1.0f32), ConstantNoise::new(-1.0),
+                                    ConstantNoise::new(0.5));
+        assert!(noise.value((0.0, 0.0, 0.0)) == (1.0, -1.0, 0.5));
+    }
+}
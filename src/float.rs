@@ -0,0 +1,28 @@
+//! Conversions between `f64` and the float type used by a generator.
+
+use num_traits::{NumCast, Float};
+
+/// Converts an `f64` (usually a constant) to the float type `F`.
+pub fn cast<F: Float>(value: f64) -> F {
+    NumCast::from(value).expect("got value that is not representable by the float type")
+}
+
+/// Converts a float to `f64`, this is lossless for `f32` and `f64`.
+pub fn to_f64<F: Float>(value: F) -> f64 {
+    value.to_f64().expect("got float that is not convertible to f64")
+}
+
+/// Returns the lattice coordinate of a position, saturating at the bounds of `i32`.
+///
+/// NaN is mapped to zero.
+pub fn floor_to_i32<F: Float>(value: F) -> i32 {
+    // float to integer casts saturate and map NaN to zero
+    to_f64(value.floor()) as i32
+}
+
+/// Returns the lattice coordinate of a position, saturating at the bounds of `i64`.
+///
+/// NaN is mapped to zero.
+pub fn floor_to_i64<F: Float>(value: F) -> i64 {
+    to_f64(value.floor()) as i64
+}
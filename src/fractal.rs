@@ -0,0 +1,435 @@
+use crate::noise::Noise;
+use num_traits::Float;
+use crate::float::{cast, to_f64};
+
+/// Derives the seed of an octave by hashing the seed together with the octave index.
+///
+/// The seeds of all octaves of a fractal noise are distinct for every seed (including `0`, `1`
+/// and `-1`) and different seeds give distinct seeds for the same octave.
+pub fn hash_seed(seed: i32, octave: usize) -> i32 {
+    // mix in the octave using the golden ratio, then apply the finalizer of MurmurHash3, both
+    // steps are bijective
+    let mut h = (seed as u32) ^ (octave as u32).wrapping_mul(0x9e3779b9);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^= h >> 16;
+    h as i32
+}
+
+/// The parameters shared by all fractal noise generators.
+///
+/// Fractal noise is generated by adding multiple octaves of a source noise, each octave having
+/// a higher frequency and usually a lower amplitude than the one before.
+#[derive(Clone, Copy)]
+pub struct Fractal {
+    /// The seed of the fractal noise, the octaves get their own seeds derived from this one.
+    pub seed: i32,
+    /// The amplitude of the first octave.
+    pub amp: f64,
+    /// The frequency of the first octave.
+    pub freq: f64,
+    /// The number of octaves.
+    pub octaves: usize,
+    /// The factor the frequency is multiplied with from one octave to the next.
+    pub lacunarity: f64,
+    /// The factor the amplitude is multiplied with from one octave to the next (also called
+    /// gain).
+    pub persistence: f64,
+    /// Derives the seed of an octave (second parameter, starting with zero) from the seed.
+    pub octave_seed: fn(i32, usize) -> i32,
+}
+
+impl Fractal {
+    /// Creates the parameters for the classic Perlin noise: The frequency doubles and the
+    /// amplitude halves from one octave to the next. The seeds of the octaves are derived using
+    /// `hash_seed`.
+    pub fn new(seed: i32, amp: f64, freq: f64, octaves: usize) -> Fractal {
+        Fractal{
+            seed,
+            amp,
+            freq,
+            octaves,
+            lacunarity: 2.0,
+            persistence: 0.5,
+            octave_seed: hash_seed,
+        }
+    }
+
+    /// Creates the source noise of all octaves by calling `factory` with the seed and the
+    /// frequency of each octave.
+    pub fn build<Src, F: Fn(i32, f64) -> Src>(&self, factory: F) -> Vec<Src> {
+        let mut noises = Vec::with_capacity(self.octaves);
+        let mut freq = self.freq;
+        for octave in 0..self.octaves {
+            noises.push(factory((self.octave_seed)(self.seed, octave), freq));
+            freq *= self.lacunarity;
+        }
+        noises
+    }
+}
+
+/// Fractal Brownian motion: Adds octaves of the source noise.
+///
+/// Works in every dimension, the source noises are created by a factory function that gets the
+/// seed and the frequency of the octave and has to return noise with an amplitude of one (e.g.
+/// `|seed, freq| blocks::new_noise_2d(seed, 1.0, freq)`).
+pub struct Fbm<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+}
+
+impl<Src> Fbm<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, factory: F) -> Fbm<Src> {
+        Fbm{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for Fbm<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut value = 0.0;
+        let mut amp = self.amp;
+        for octave in self.octaves.iter() {
+            value += amp * to_f64(octave.value(position.clone()));
+            amp *= self.persistence;
+        }
+        cast(value)
+    }
+}
+
+/// Musgrave's ridged multifractal noise.
+///
+/// Every octave is folded to `(offset - |n|)^2`, which forms sharp ridges where the source
+/// noise crosses zero, and weighted by the signal of the previous octave, so that the ridges get
+/// rough while the valleys stay smooth. This is well suited for mountain ranges. The amplitude
+/// of the octaves falls with `persistence`. The result is scaled the same way libnoise does it,
+/// with `offset = 1` and `gain = 2` it is between `-amp` and about `1.5 * amp`.
+///
+/// See `Fbm` for the requirements of the factory.
+pub struct RidgedMulti<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+    offset: f64,
+    gain: f64,
+}
+
+impl<Src> RidgedMulti<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, offset: f64, gain: f64, factory: F) -> RidgedMulti<Src> {
+        RidgedMulti{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence,
+            offset,
+            gain
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for RidgedMulti<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut value = 0.0;
+        let mut weight = 1.0;
+        let mut spectral_weight = 1.0;
+        for octave in self.octaves.iter() {
+            let mut signal = self.offset - to_f64(octave.value(position.clone())).abs();
+            signal *= signal;
+            signal *= weight;
+
+            // the signal of this octave weights the next one
+            weight = (signal * self.gain).clamp(0.0, 1.0);
+
+            value += signal * spectral_weight;
+            spectral_weight *= self.persistence;
+        }
+        cast((value * 1.25 - 1.0) * self.amp)
+    }
+}
+
+/// Billowy fractal noise, good for clouds and smoke.
+///
+/// Like `Fbm`, but every octave is folded to `2 * |n| - 1` before it is added, which gives
+/// round, billowy lumps. As libnoise does it, `0.5` is added to the sum before it is multiplied
+/// with `amp`.
+///
+/// See `Fbm` for the requirements of the factory.
+pub struct Billow<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+}
+
+impl<Src> Billow<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, factory: F) -> Billow<Src> {
+        Billow{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for Billow<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut value = 0.0;
+        let mut amp = 1.0;
+        for octave in self.octaves.iter() {
+            value += amp * (2.0 * to_f64(octave.value(position.clone())).abs() - 1.0);
+            amp *= self.persistence;
+        }
+        cast((value + 0.5) * self.amp)
+    }
+}
+
+/// Perlin's turbulence: Adds the absolute values of the octaves.
+///
+/// The result is never negative, the creases where the octaves cross zero give the look of
+/// turbulent fluids and fire.
+///
+/// See `Fbm` for the requirements of the factory.
+pub struct Turbulence<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+}
+
+impl<Src> Turbulence<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, factory: F) -> Turbulence<Src> {
+        Turbulence{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for Turbulence<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut value = 0.0;
+        let mut amp = self.amp;
+        for octave in self.octaves.iter() {
+            value += amp * to_f64(octave.value(position.clone())).abs();
+            amp *= self.persistence;
+        }
+        cast(value)
+    }
+}
+
+/// Musgrave's hybrid multifractal noise.
+///
+/// Every octave is shifted by `offset` and weighted by the product of the previous octaves, so
+/// low areas stay smooth while high areas get rough. The weight of an octave falls with
+/// `persistence`, the result is multiplied with `amp`. See "Texturing and Modeling: A
+/// Procedural Approach" for details.
+///
+/// See `Fbm` for the requirements of the factory.
+pub struct HybridMulti<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+    offset: f64,
+}
+
+impl<Src> HybridMulti<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, offset: f64, factory: F) -> HybridMulti<Src> {
+        HybridMulti{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence,
+            offset
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for HybridMulti<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut value = 0.0;
+        let mut weight = 1.0;
+        let mut spectral_weight = 1.0;
+        for octave in self.octaves.iter() {
+            if weight > 1.0 {
+                weight = 1.0;
+            }
+            let signal = (to_f64(octave.value(position.clone())) + self.offset) * spectral_weight;
+            value += weight * signal;
+            weight *= signal;
+            spectral_weight *= self.persistence;
+        }
+        cast(value * self.amp)
+    }
+}
+
+/// Musgrave's heterogeneous terrain.
+///
+/// Every octave is shifted by `offset` and scaled by the sum of the previous octaves, so
+/// the higher the terrain already is, the rougher it gets. The weight of an octave falls with
+/// `persistence`, the result is multiplied with `amp`. See "Texturing and Modeling: A
+/// Procedural Approach" for details.
+///
+/// See `Fbm` for the requirements of the factory.
+pub struct HeteroTerrain<Src> {
+    octaves: Vec<Src>,
+    amp: f64,
+    persistence: f64,
+    offset: f64,
+}
+
+impl<Src> HeteroTerrain<Src> {
+    pub fn new<F: Fn(i32, f64) -> Src>(fractal: Fractal, offset: f64, factory: F) -> HeteroTerrain<Src> {
+        HeteroTerrain{
+            octaves: fractal.build(factory),
+            amp: fractal.amp,
+            persistence: fractal.persistence,
+            offset
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src: Noise<In, Out=F>> Noise<In> for HeteroTerrain<Src> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let mut octaves = self.octaves.iter();
+        let mut value = match octaves.next() {
+            Some(octave) => to_f64(octave.value(position.clone())) + self.offset,
+            None => return F::zero(),
+        };
+        let mut spectral_weight = self.persistence;
+        for octave in octaves {
+            value += (to_f64(octave.value(position.clone())) + self.offset) * spectral_weight * value;
+            spectral_weight *= self.persistence;
+        }
+        cast(value * self.amp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Fractal, Fbm, RidgedMulti, Billow, Turbulence, HybridMulti, HeteroTerrain, hash_seed};
+    use crate::default_noise::{ConstantNoise, NoOpNoise, DefaultI32Noise};
+    use crate::noise::Noise;
+    use num_traits::Float;
+
+    fn correlation(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() as f64;
+        let mean_a = a.iter().fold(0.0, |acc, x| acc + x) / n;
+        let mean_b = b.iter().fold(0.0, |acc, x| acc + x) / n;
+        let mut cov = 0.0;
+        let mut var_a = 0.0;
+        let mut var_b = 0.0;
+        for (x, y) in a.iter().zip(b.iter()) {
+            cov += (x - mean_a) * (y - mean_b);
+            var_a += (x - mean_a) * (x - mean_a);
+            var_b += (y - mean_b) * (y - mean_b);
+        }
+        cov / (var_a * var_b).sqrt()
+    }
+
+    #[test]
+    fn hash_seed_test() {
+        let seeds: [i32; 6] = [0, 1, -1, 2, i32::MAX, i32::MIN];
+        for seed in seeds.iter() {
+            let octaves: Vec<i32> = (0..8).map(|octave| hash_seed(*seed, octave)).collect();
+            for a in 0..octaves.len() {
+                for b in (a + 1)..octaves.len() {
+                    assert!(octaves[a] != octaves[b]);
+                }
+            }
+
+            // the noise of the octaves must not be correlated
+            let values: Vec<Vec<f64>> = octaves.iter().map(|octave_seed| {
+                let noise = DefaultI32Noise::new(*octave_seed);
+                (0..1000).map(|i| noise.value(i)).collect()
+            }).collect();
+            for a in 0..values.len() {
+                for b in (a + 1)..values.len() {
+                    assert!(correlation(&values[a], &values[b]).abs() < 0.15);
+                }
+            }
+        }
+
+        for octave in 0..8 {
+            assert!(hash_seed(0, octave) != hash_seed(1, octave));
+            assert!(hash_seed(1, octave) != hash_seed(-1, octave));
+        }
+    }
+
+    #[test]
+    fn fbm_test() {
+        // each octave returns its frequency
+        let noise = Fbm::new(Fractal::new(0, 1.0, 1.0, 3), |_, freq| { ConstantNoise::new(freq) });
+        assert!((noise.value(0.0) - (1.0 + 0.5 * 2.0 + 0.25 * 4.0)).abs() < 0.0001);
+
+        let mut fractal = Fractal::new(0, 2.0, 1.0, 3);
+        fractal.lacunarity = 3.0;
+        fractal.persistence = 0.25;
+        let noise = Fbm::new(fractal, |_, freq| { ConstantNoise::new(freq) });
+        assert!((noise.value((1.0, 2.0)) - 2.0 * (1.0 + 0.25 * 3.0 + 0.0625 * 9.0)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn ridged_multi_test() {
+        let noise = RidgedMulti::new(Fractal::new(0, 1.0, 1.0, 3), 1.0, 2.0, |_, _| { ConstantNoise::new(0.0) });
+        assert!((noise.value(0.0) - ((1.0 + 0.5 + 0.25) * 1.25 - 1.0)).abs() < 0.0001);
+
+        // the weight of the second octave is limited by the first octave
+        let noise = RidgedMulti::new(Fractal::new(0, 1.0, 1.0, 2), 1.0, 2.0, |_, _| { NoOpNoise });
+        let signal = 0.5 * 0.5;
+        let expected = signal + 0.5 * signal * signal * 2.0;
+        assert!((noise.value(0.5) - (expected * 1.25 - 1.0)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn billow_test() {
+        let noise = Billow::new(Fractal::new(0, 2.0, 1.0, 2), |_, _| { ConstantNoise::new(-0.5) });
+        assert!((noise.value(0.0) - (0.0 + 0.5 * 0.0 + 0.5) * 2.0).abs() < 0.0001);
+
+        let noise = Billow::new(Fractal::new(0, 1.0, 1.0, 2), |_, _| { NoOpNoise });
+        assert!((noise.value(-1.0) - (1.0 + 0.5 * 1.0 + 0.5)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn turbulence_test() {
+        let noise = Turbulence::new(Fractal::new(0, 2.0, 1.0, 3), |_, _| { NoOpNoise });
+        for i in -10..10 {
+            let f = i as f64 / 10.0;
+            assert!((noise.value(f) - 2.0 * f.abs() * 1.75).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn hybrid_multi_test() {
+        let noise = HybridMulti::new(Fractal::new(0, 2.0, 1.0, 3), 0.5, |_, _| { ConstantNoise::new(0.5) });
+        // signals: 1, 0.5, 0.25 - weights: 1, 1, 0.5
+        assert!((noise.value(0.0) - 2.0 * (1.0 + 0.5 + 0.5 * 0.25)).abs() < 0.0001);
+
+        let noise = HybridMulti::new(Fractal::new(0, 1.0, 1.0, 3), 0.0, |_, _| { ConstantNoise::new(0.0) });
+        assert!(noise.value(0.0) == 0.0);
+    }
+
+    #[test]
+    fn hetero_terrain_test() {
+        let noise = HeteroTerrain::new(Fractal::new(0, 2.0, 1.0, 3), 0.5, |_, _| { ConstantNoise::new(0.5) });
+        // 1, then 1 + 1 * 0.5 * 1 = 1.5, then 1.5 + 1 * 0.25 * 1.5 = 1.875
+        assert!((noise.value(0.0) - 2.0 * 1.875).abs() < 0.0001);
+
+        let noise = HeteroTerrain::new(Fractal::new(0, 1.0, 1.0, 0), 0.5, |_, _| { ConstantNoise::new(0.5) });
+        assert!(noise.value(0.0) == 0.0);
+    }
+}
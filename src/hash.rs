@@ -0,0 +1,180 @@
+//! Hash functions that turn integer lattice coordinates into pseudo-random numbers.
+//!
+//! All lattice based generators (gradient, value, simplex and cellular noise) get their
+//! randomness from a `LatticeHash`. `DefaultI32Noise` is the default and reproduces the
+//! results of earlier versions of this crate, `PermutationTable` is the classic approach of
+//! Perlin's reference implementation and `PcgHash` has the best statistical quality.
+
+use crate::lattice::{fold, index_2d, index_3d, index_4d};
+
+/// Maps integer lattice coordinates to pseudo-random 32 bit values.
+///
+/// The generators use the most significant bits of the result, a hash producing less than 32
+/// random bits has to place them at the top. Only `hash1` is required, the other dimensions
+/// combine the coordinates to a single one by default. All implementations in this crate give
+/// the same results for coordinates in the range of `i32` as they did before the coordinates
+/// were widened to `i64`.
+pub trait LatticeHash {
+    fn hash1(&self, x: i64) -> u32;
+
+    fn hash2(&self, x: i64, y: i64) -> u32 {
+        self.hash1(index_2d(fold(x), fold(y)) as i64)
+    }
+
+    fn hash3(&self, x: i64, y: i64, z: i64) -> u32 {
+        self.hash1(index_3d(fold(x), fold(y), fold(z)) as i64)
+    }
+
+    fn hash4(&self, x: i64, y: i64, z: i64, w: i64) -> u32 {
+        self.hash1(index_4d(fold(x), fold(y), fold(z), fold(w)) as i64)
+    }
+}
+
+/// Perlin's permutation table of the numbers 0 to 255, shuffled using a seed.
+///
+/// Every byte of every coordinate (folded to 32 bits) is mixed into the hash by one table
+/// lookup. This is cheap, but there are only 256 different results. They are placed in the
+/// middle of their 1/256th of the `u32` range, so the generators see values symmetric around
+/// zero.
+pub struct PermutationTable {
+    values: [u8; 256],
+}
+
+impl PermutationTable {
+    pub fn new(seed: i32) -> PermutationTable {
+        let mut values = [0u8; 256];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+        // Fisher-Yates shuffle
+        let hash = PcgHash::new(seed);
+        for i in (1..256).rev() {
+            let j = (hash.hash1(i as i64) as usize) % (i + 1);
+            values.swap(i, j);
+        }
+        PermutationTable{
+            values
+        }
+    }
+
+    fn mix(&self, hash: u8, coordinate: i64) -> u8 {
+        let c = fold(coordinate) as u32;
+        let hash = self.values[(hash ^ (c as u8)) as usize];
+        let hash = self.values[(hash ^ ((c >> 8) as u8)) as usize];
+        let hash = self.values[(hash ^ ((c >> 16) as u8)) as usize];
+        self.values[(hash ^ ((c >> 24) as u8)) as usize]
+    }
+
+    fn spread(hash: u8) -> u32 {
+        ((hash as u32) << 24) | 0x00800000
+    }
+}
+
+impl LatticeHash for PermutationTable {
+    fn hash1(&self, x: i64) -> u32 {
+        PermutationTable::spread(self.mix(0, x))
+    }
+
+    fn hash2(&self, x: i64, y: i64) -> u32 {
+        PermutationTable::spread(self.mix(self.mix(0, x), y))
+    }
+
+    fn hash3(&self, x: i64, y: i64, z: i64) -> u32 {
+        PermutationTable::spread(self.mix(self.mix(self.mix(0, x), y), z))
+    }
+
+    fn hash4(&self, x: i64, y: i64, z: i64, w: i64) -> u32 {
+        PermutationTable::spread(self.mix(self.mix(self.mix(self.mix(0, x), y), z), w))
+    }
+}
+
+/// Integer hash based on the output permutation of the PCG random number generator.
+///
+/// See Jarzynski and Olano, [Hash Functions for GPU Rendering](http://jcgt.org/published/0009/03/02/).
+/// Each coordinate is folded to 32 bits and added to the hash of the previous ones, so every
+/// bit of the result depends on every bit of the seed and the coordinates.
+#[derive(Clone, Copy)]
+pub struct PcgHash {
+    seed: u32,
+}
+
+impl PcgHash {
+    pub fn new(seed: i32) -> PcgHash {
+        PcgHash{
+            seed: pcg(seed as u32)
+        }
+    }
+}
+
+fn pcg(input: u32) -> u32 {
+    let state = input.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+impl LatticeHash for PcgHash {
+    fn hash1(&self, x: i64) -> u32 {
+        pcg(self.seed.wrapping_add(fold(x) as u32))
+    }
+
+    fn hash2(&self, x: i64, y: i64) -> u32 {
+        pcg(self.hash1(x).wrapping_add(fold(y) as u32))
+    }
+
+    fn hash3(&self, x: i64, y: i64, z: i64) -> u32 {
+        pcg(self.hash2(x, y).wrapping_add(fold(z) as u32))
+    }
+
+    fn hash4(&self, x: i64, y: i64, z: i64, w: i64) -> u32 {
+        pcg(self.hash3(x, y, z).wrapping_add(fold(w) as u32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LatticeHash, PermutationTable, PcgHash};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::lattice::unit;
+    use crate::noise::Noise;
+
+    #[test]
+    fn permutation_table_test() {
+        let table = PermutationTable::new(0);
+        let mut seen = [false; 256];
+        for value in table.values.iter() {
+            seen[*value as usize] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+        assert!(PermutationTable::new(1).values.iter().zip(table.values.iter()).any(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn hash_distribution_test() {
+        fn check<H: LatticeHash>(hash: H) {
+            let mut sum = 0.0;
+            let mut buckets = [0; 16];
+            for x in -50..50 {
+                for y in -50..50 {
+                    let value = unit(hash.hash2(x, y));
+                    assert!(-1.0 < value && value <= 1.0);
+                    sum += value;
+                    buckets[(hash.hash3(x, y, 7) >> 28) as usize] += 1;
+                }
+            }
+            assert!((sum / 10000.0).abs() < 0.05);
+            // 625 per bucket are expected
+            assert!(buckets.iter().all(|b| 500 < *b && *b < 750));
+        }
+        check(DefaultI32Noise::new(0));
+        check(PermutationTable::new(0));
+        check(PcgHash::new(0));
+    }
+
+    #[test]
+    fn default_noise_hash_test() {
+        let noise = DefaultI32Noise::new(42);
+        for i in -1000..1000 {
+            assert!(unit(noise.hash1(i * 7919)) == noise.value(i * 7919));
+        }
+    }
+}
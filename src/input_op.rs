@@ -1,4 +1,4 @@
-use noise::Noise;
+use crate::noise::Noise;
 
 /// Applies an operation on the position parameter before the
 /// position is passed to the source noise.
@@ -10,8 +10,8 @@ pub struct InputOp<Src, Op> {
 impl<Src, Op> InputOp<Src, Op> {
 	pub fn new(source: Src, op: Op) -> InputOp<Src, Op> {
 		InputOp{
-			source: source,
-			op: op
+			source,
+			op
 		}
 	}
 }
@@ -30,8 +30,8 @@ impl<In, Out, OpOut, Src: Noise<OpOut, Out=Out>, Op: Fn(In) -> OpOut>
 #[cfg(test)]
 mod test {
 	use super::InputOp;
-	use default_noise::NoOpNoise;
-	use noise::Noise;
+	use crate::default_noise::NoOpNoise;
+	use crate::noise::Noise;
 
 	#[test]
 	fn input_op_test() {
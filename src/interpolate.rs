@@ -2,8 +2,9 @@
 //!
 //! Interpolation is required to generate smooth noise.
 
-use std::num::Float;
+use num_traits::Float;
 use std::f64::consts::PI;
+use crate::float::cast;
 
 /// Interface to interpolate beweeen two numbers.
 pub trait Interpolator<F: Float> {
@@ -19,9 +20,9 @@ pub trait Interpolator<F: Float> {
     /// The following law is sensible but not stricly required:
     ///
     /// * `interpolate(a, b, p) == a + b - interpolate(a, b, 1 - p)`: This means that the function
-    /// has to be mirrored around the center point of the interpolated area. Literally all sane
-    /// interpolation functions obey this law. This also implies that
-    /// `interpolate(a, b, 0.5) == (a + b) / 2`.
+    ///   has to be mirrored around the center point of the interpolated area. Literally all sane
+    ///   interpolation functions obey this law. This also implies that
+    ///   `interpolate(a, b, 0.5) == (a + b) / 2`.
     fn interpolate(&self, a: F, b: F, percent: F) -> F;
 }
 
@@ -32,10 +33,10 @@ pub trait Interpolator<F: Float> {
 /// function looks like.
 pub struct LinearInterpolator;
 
-impl Interpolator<f64> for LinearInterpolator {
-    fn interpolate(&self, a: f64, b: f64, percent: f64) -> f64 {
-        debug_assert!(0.0 <= percent && percent <= 1.0);
-        a * (1.0 - percent) + b * percent
+impl<F: Float> Interpolator<F> for LinearInterpolator {
+    fn interpolate(&self, a: F, b: F, percent: F) -> F {
+        debug_assert!(F::zero() <= percent && percent <= F::one());
+        a * (F::one() - percent) + b * percent
     }
 }
 
@@ -47,11 +48,11 @@ impl Interpolator<f64> for LinearInterpolator {
 /// looks like.
 pub struct PerlinInterpolator;
 
-impl Interpolator<f64> for PerlinInterpolator {
-    fn interpolate(&self, a: f64, b: f64, percent: f64) -> f64 {
-        debug_assert!(0.0 <= percent && percent <= 1.0);
+impl<F: Float> Interpolator<F> for PerlinInterpolator {
+    fn interpolate(&self, a: F, b: F, percent: F) -> F {
+        debug_assert!(F::zero() <= percent && percent <= F::one());
         // 3x^2 - 2x^3
-        let x = percent * percent * (3.0 - 2.0 * percent);
+        let x = percent * percent * (cast::<F>(3.0) - cast::<F>(2.0) * percent);
         LinearInterpolator.interpolate(a, b, x)
     }
 }
@@ -64,10 +65,10 @@ impl Interpolator<f64> for PerlinInterpolator {
 /// an idea of how this looks like.
 pub struct CosInterpolator;
 
-impl Interpolator<f64> for CosInterpolator {
-    fn interpolate(&self, a: f64, b: f64, percent: f64) -> f64 {
-        debug_assert!(0.0 <= percent && percent <= 1.0);
-        let x = (1.0 - (percent * PI).cos()) / 2.0;
+impl<F: Float> Interpolator<F> for CosInterpolator {
+    fn interpolate(&self, a: F, b: F, percent: F) -> F {
+        debug_assert!(F::zero() <= percent && percent <= F::one());
+        let x = (F::one() - (percent * cast(PI)).cos()) / cast(2.0);
         LinearInterpolator.interpolate(a, b, x)
     }
 }
@@ -80,11 +81,10 @@ pub fn sawtooth<F: Float>(x: F) -> F {
 #[cfg(test)]
 mod test {
     use super::{LinearInterpolator, PerlinInterpolator, CosInterpolator, Interpolator, sawtooth};
-    use std::num::Float;
 
     #[test]
     fn interpolate_linear_test() {
-        let a = 10.0;
+        let a: f64 = 10.0;
         let b = 20.0;
         let result = LinearInterpolator.interpolate(a, b, 0.75);
         assert!((result - 17.5).abs() < 0.0001);
@@ -92,7 +92,7 @@ mod test {
 
     #[test]
     fn interpolate_perlin_test() {
-        let a = 10.0;
+        let a: f64 = 10.0;
         let b = 20.0;
         let result = PerlinInterpolator.interpolate(a, b, 0.75);
         assert!((result - 18.4375).abs() < 0.0001);
@@ -100,12 +100,22 @@ mod test {
 
     #[test]
     fn interpolate_cos_test() {
-        let a = 10.0;
+        let a: f64 = 10.0;
         let b = 20.0;
         let result = CosInterpolator.interpolate(a, b, 0.75);
         assert!((result - 18.5355).abs() < 0.0001);
     }
 
+    #[test]
+    fn interpolate_f32_test() {
+        let linear: f32 = LinearInterpolator.interpolate(10.0, 20.0, 0.75);
+        assert!((linear - 17.5).abs() < 0.0001);
+        let perlin: f32 = PerlinInterpolator.interpolate(10.0, 20.0, 0.75);
+        assert!((perlin - 18.4375).abs() < 0.0001);
+        let cos: f32 = CosInterpolator.interpolate(10.0, 20.0, 0.75);
+        assert!((cos - 18.5355).abs() < 0.0001);
+    }
+
     #[test]
     fn sawtooth_test() {
         assert!(sawtooth(0.0) == 0.0);
@@ -1,42 +1,46 @@
-use interpolate::{Interpolator, sawtooth};
-use noise::Noise;
-use std::num::{NumCast, Float};
-
-static X_PRIME: i32 = 1073741827;
-static Y_PRIME: i32 = 53688275;
-
-fn float_to_i32<F: Float>(f: F) -> i32 {
-    NumCast::from(f).expect("got position that is not convertible to i32")
-}
+use crate::interpolate::{Interpolator, sawtooth};
+use crate::noise::Noise;
+use num_traits::Float;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+use crate::lattice::{X_PRIME, Y_PRIME, Z_PRIME, W_PRIME, LatticeCoordinate, fold, index_2d,
+                     unit, select_hash};
+use crate::hash::LatticeHash;
+use crate::float::{cast, floor_to_i64};
 
 /// Takes discrete input noise to produce value-interpolated
 /// output noise.
-pub struct InterpolatedNoise<Src, I> {
+///
+/// The source is sampled at lattice coordinates of type `L` (`i32` or `i64`). Positions
+/// outside of the range of `L` are clamped to it.
+pub struct InterpolatedNoise<Src, I, L = i32> {
     source: Src,
     interpolator: I,
+    lattice: PhantomData<L>,
 }
 
-impl<Src, I> InterpolatedNoise<Src, I> {
+impl<Src, I, L> InterpolatedNoise<Src, I, L> {
     /// Creates a new interpolated noise using the given source noise and the given
     /// interpolator.
-    pub fn new(source: Src, interpolator: I) -> InterpolatedNoise<Src, I> {
+    pub fn new(source: Src, interpolator: I) -> InterpolatedNoise<Src, I, L> {
         InterpolatedNoise{
-            source: source,
-            interpolator: interpolator
+            source,
+            interpolator,
+            lattice: PhantomData
         }
     }
 }
 
-// TODO remove the i32 restriction to use any intregral type!
-impl<InOut: Float, Src: Noise<i32, Out=InOut>, I: Interpolator<InOut>>
+impl<InOut: Float, L: LatticeCoordinate, Src: Noise<L, Out=InOut>, I: Interpolator<InOut>>
         Noise<InOut>
-        for InterpolatedNoise<Src, I> {
+        for InterpolatedNoise<Src, I, L> {
 
     type Out = InOut;
 
     fn value(&self, position: InOut) -> InOut {
-        let a = float_to_i32(position.floor());
-        let b = a + 1;
+        let a = L::floor(position);
+        let b = a.next();
         let p = sawtooth(position);
         self.interpolator.interpolate(
             self.source.value(a),
@@ -45,65 +49,156 @@ impl<InOut: Float, Src: Noise<i32, Out=InOut>, I: Interpolator<InOut>>
     }
 }
 
-/// Produces two-dimensional gradient-noise using discrete one-dimensional source noise.
+/// Produces one-dimensional gradient-noise using a lattice hash.
+///
+/// Unlike `InterpolatedNoise` the hash is not used as the value at the integer lattice points,
+/// but as the slope of the noise there, the noise itself is zero at all lattice points. This
+/// gives the same spectrum as the other gradient noises. The result is between [-1; 1].
+pub struct GradientNoise1D<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        GradientNoise1D<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> GradientNoise1D<H, I> {
+        GradientNoise1D{
+            hash,
+            interpolator
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<F>
+        for GradientNoise1D<H, I> {
+
+    type Out = F;
+
+    fn value(&self, position: F) -> F {
+        let a = floor_to_i64(position);
+        let b = a.wrapping_add(1);
+        let p = sawtooth(position);
+        let n0 = cast::<F>(unit(self.hash.hash1(a))) * p;
+        let n1 = cast::<F>(unit(self.hash.hash1(b))) * (p - F::one());
+        // the largest possible value is 0.5 (slopes 1 and -1 meeting in the middle)
+        cast::<F>(2.0) * self.interpolator.interpolate(n0, n1, p)
+    }
+}
+
+/// The number of gradients used by `InterpolatedNoise2D::new`, one for every value of a
+/// `PermutationTable`.
+pub static DEFAULT_GRADIENT_COUNT: usize = 256;
+
+// One table for every supported gradient count (the powers of two up to 4096), built on first
+// use and shared by all noises.
+static GRADIENT_TABLES: [OnceLock<Vec<(f64, f64)>>; 13] = [const { OnceLock::new() }; 13];
+
+fn gradient_table(count: usize) -> &'static [(f64, f64)] {
+    assert!(count.is_power_of_two() && count <= 4096,
+            "the number of gradients must be a power of two up to 4096");
+    GRADIENT_TABLES[count.trailing_zeros() as usize].get_or_init(|| {
+        (0..count).map(|i| {
+            let angle = 2.0 * PI * (i as f64) / (count as f64);
+            (angle.cos(), angle.sin())
+        }).collect()
+    })
+}
+
+/// Selects how the gradients of `InterpolatedNoise2D` are chosen.
+#[derive(Clone, Copy)]
+pub enum Gradients {
+    /// The hash picks one of the given number of unit vectors evenly spaced around the circle.
+    /// The number has to be a power of two up to 4096.
+    Table(usize),
+    /// Rejection sampling of random vectors within the unit circle, as done by earlier
+    /// versions of this crate. This reproduces their results, but needs an unbounded number
+    /// of hash calls per gradient.
+    Legacy,
+}
+
+/// Produces two-dimensional gradient-noise using a lattice hash.
 ///
 /// See also <https://en.wikipedia.org/wiki/Perlin_noise> for the implementation.
-/// The source noise is required to be between [-1; 1] or [0; 1] and should be
-/// uniformly distributed.
-// TODO remove the i32 and f64 restrictions!
-pub struct InterpolatedNoise2D<Src: Noise<i32, Out=f64>, I: Interpolator<f64>> {
-    source: Src,
+pub struct InterpolatedNoise2D<H: LatticeHash, I> {
+    hash: H,
     interpolator: I,
+    // empty for `Gradients::Legacy`
+    gradients: &'static [(f64, f64)],
 }
 
-impl<Src: Noise<i32, Out=f64>, I: Interpolator<f64>>
-        InterpolatedNoise2D<Src, I> {
+impl<H: LatticeHash, I>
+        InterpolatedNoise2D<H, I> {
 
-    pub fn new(source: Src, interpolator: I) -> InterpolatedNoise2D<Src, I> {
+    pub fn new(hash: H, interpolator: I) -> InterpolatedNoise2D<H, I> {
+        InterpolatedNoise2D::with_gradients(hash, interpolator, Gradients::Table(DEFAULT_GRADIENT_COUNT))
+    }
+
+    /// Creates a new noise using the given way to choose the gradients.
+    ///
+    /// Panics if the number of gradients of the table is not supported.
+    pub fn with_gradients(hash: H, interpolator: I, gradients: Gradients) -> InterpolatedNoise2D<H, I> {
+        let gradients = match gradients {
+            Gradients::Table(count) => gradient_table(count),
+            Gradients::Legacy => &[],
+        };
         InterpolatedNoise2D{
-            source: source,
-            interpolator: interpolator
+            hash,
+            interpolator,
+            gradients
         }
     }
 
-    fn gradient(&self, x: i32, y: i32) -> (f64, f64) {
-        let mut x_index = x * X_PRIME + y * Y_PRIME;
-        let mut y_index = x_index + 15268783;
+    fn gradient(&self, x: i64, y: i64) -> (f64, f64) {
+        if self.gradients.is_empty() {
+            self.legacy_gradient(x, y)
+        } else {
+            self.gradients[select_hash(self.hash.hash2(x, y), self.gradients.len())]
+        }
+    }
+
+    fn legacy_gradient(&self, x: i64, y: i64) -> (f64, f64) {
+        let mut x_index = index_2d(fold(x), fold(y));
+        let mut y_index = x_index.wrapping_add(15268783);
         loop {
-            let x = self.source.value(x_index);
-            let y = self.source.value(y_index);
+            let x = unit(self.hash.hash1(x_index as i64));
+            let y = unit(self.hash.hash1(y_index as i64));
             let dist = x.powi(2) + y.powi(2);
-            if dist <= 1.0 {
+            // the zero vector can not be normalized
+            if dist <= 1.0 && dist > 0.0 {
                 let norm = dist.sqrt();
                 return (x / norm, y / norm);
             }
-            x_index *= 6684817;
-            y_index *= 5684659;
+            x_index = x_index.wrapping_mul(6684817);
+            y_index = y_index.wrapping_mul(5684659);
         }
     }
 }
 
-impl<Src: Noise<i32, Out=f64>, I: Interpolator<f64>>
-        Noise<(f64, f64)>
-        for InterpolatedNoise2D<Src, I> {
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<(F, F)>
+        for InterpolatedNoise2D<H, I> {
 
-    type Out = f64;
+    type Out = F;
 
-    fn value(&self, (pos_x, pos_y): (f64, f64)) -> f64 {
+    fn value(&self, (pos_x, pos_y): (F, F)) -> F {
 
-        fn dot(pos_x: f64, pos_y: f64, grad_x: i32, grad_y: i32, (gx, gy): (f64, f64)) -> f64 {
-            let dx = pos_x - (grad_x as f64);
-            let dy = pos_y - (grad_y as f64);
-            dx * gx + dy * gy
+        // the offsets to the lattice point are taken from the fractional part of the position,
+        // this keeps the precision of `f32` positions far away from the origin
+        fn dot<F: Float>(dx: F, dy: F, (gx, gy): (f64, f64)) -> F {
+            dx * cast(gx) + dy * cast(gy)
         }
 
-        let x0 = pos_x.floor() as i32;
-        let x1 = x0 + 1;
-        let y0 = pos_y.floor() as i32;
-        let y1 = y0 + 1;
+        let x0 = floor_to_i64(pos_x);
+        let x1 = x0.wrapping_add(1);
+        let y0 = floor_to_i64(pos_y);
+        let y1 = y0.wrapping_add(1);
 
         let px = sawtooth(pos_x);
         let py = sawtooth(pos_y);
+        let qx = px - F::one();
+        let qy = py - F::one();
 
         // gradXY for X,Y <- {0,1}
         let grad00 = self.gradient(x0, y0);
@@ -111,10 +206,10 @@ impl<Src: Noise<i32, Out=f64>, I: Interpolator<f64>>
         let grad10 = self.gradient(x1, y0);
         let grad11 = self.gradient(x1, y1);
 
-        let n00 = dot(pos_x, pos_y, x0, y0, grad00);
-        let n01 = dot(pos_x, pos_y, x0, y1, grad01);
-        let n10 = dot(pos_x, pos_y, x1, y0, grad10);
-        let n11 = dot(pos_x, pos_y, x1, y1, grad11);
+        let n00 = dot(px, py, grad00);
+        let n01 = dot(px, qy, grad01);
+        let n10 = dot(qx, py, grad10);
+        let n11 = dot(qx, qy, grad11);
 
         // interpolate both x directions
         let val1 = self.interpolator.interpolate(n00, n10, px);
@@ -125,14 +220,346 @@ impl<Src: Noise<i32, Out=f64>, I: Interpolator<f64>>
     }
 }
 
+// The directions to the midpoints of the twelve edges of a cube, as used by improved Perlin
+// noise, scaled to unit length.
+static GRADIENTS_3D: [(f64, f64, f64); 12] = [
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0), (-FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0), (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0),
+    (FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2), (-FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2), (-FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2),
+    (0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2), (0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2), (0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+/// Produces three-dimensional gradient-noise using a lattice hash.
+///
+/// This is the three-dimensional counterpart of `InterpolatedNoise2D`. The hash picks one of
+/// twelve gradients pointing to the edges of a cube.
+pub struct InterpolatedNoise3D<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        InterpolatedNoise3D<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> InterpolatedNoise3D<H, I> {
+        InterpolatedNoise3D{
+            hash,
+            interpolator
+        }
+    }
+
+    fn gradient(&self, x: i64, y: i64, z: i64) -> (f64, f64, f64) {
+        GRADIENTS_3D[select_hash(self.hash.hash3(x, y, z), GRADIENTS_3D.len())]
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<(F, F, F)>
+        for InterpolatedNoise3D<H, I> {
+
+    type Out = F;
+
+    fn value(&self, (pos_x, pos_y, pos_z): (F, F, F)) -> F {
+
+        fn dot<F: Float>(dx: F, dy: F, dz: F, (gx, gy, gz): (f64, f64, f64)) -> F {
+            dx * cast(gx) + dy * cast(gy) + dz * cast(gz)
+        }
+
+        let x0 = floor_to_i64(pos_x);
+        let x1 = x0.wrapping_add(1);
+        let y0 = floor_to_i64(pos_y);
+        let y1 = y0.wrapping_add(1);
+        let z0 = floor_to_i64(pos_z);
+        let z1 = z0.wrapping_add(1);
+
+        let px = sawtooth(pos_x);
+        let py = sawtooth(pos_y);
+        let pz = sawtooth(pos_z);
+        let qx = px - F::one();
+        let qy = py - F::one();
+        let qz = pz - F::one();
+
+        // nXYZ for X,Y,Z <- {0,1}
+        let n000 = dot(px, py, pz, self.gradient(x0, y0, z0));
+        let n001 = dot(px, py, qz, self.gradient(x0, y0, z1));
+        let n010 = dot(px, qy, pz, self.gradient(x0, y1, z0));
+        let n011 = dot(px, qy, qz, self.gradient(x0, y1, z1));
+        let n100 = dot(qx, py, pz, self.gradient(x1, y0, z0));
+        let n101 = dot(qx, py, qz, self.gradient(x1, y0, z1));
+        let n110 = dot(qx, qy, pz, self.gradient(x1, y1, z0));
+        let n111 = dot(qx, qy, qz, self.gradient(x1, y1, z1));
+
+        // interpolate all four x directions
+        let val00 = self.interpolator.interpolate(n000, n100, px);
+        let val01 = self.interpolator.interpolate(n001, n101, px);
+        let val10 = self.interpolator.interpolate(n010, n110, px);
+        let val11 = self.interpolator.interpolate(n011, n111, px);
+
+        // interpolate both y directions
+        let val0 = self.interpolator.interpolate(val00, val10, py);
+        let val1 = self.interpolator.interpolate(val01, val11, py);
+
+        // interpolate in z direction
+        self.interpolator.interpolate(val0, val1, pz)
+    }
+}
+
+const FRAC_1_SQRT_3: f64 = 0.5773502691896258;
+
+// The directions to the midpoints of the 32 edges of a tesseract, scaled to unit length.
+static GRADIENTS_4D: [(f64, f64, f64, f64); 32] = [
+    (0.0, FRAC_1_SQRT_3, FRAC_1_SQRT_3, FRAC_1_SQRT_3), (0.0, FRAC_1_SQRT_3, FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (0.0, FRAC_1_SQRT_3, -FRAC_1_SQRT_3, FRAC_1_SQRT_3), (0.0, FRAC_1_SQRT_3, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (0.0, -FRAC_1_SQRT_3, FRAC_1_SQRT_3, FRAC_1_SQRT_3), (0.0, -FRAC_1_SQRT_3, FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (0.0, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3, FRAC_1_SQRT_3), (0.0, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3, FRAC_1_SQRT_3), (FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3, FRAC_1_SQRT_3), (FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (-FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3, FRAC_1_SQRT_3), (-FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (-FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3, FRAC_1_SQRT_3), (-FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3),
+    (FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3), (FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3),
+    (FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3), (FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3),
+    (-FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3), (-FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3),
+    (-FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0, FRAC_1_SQRT_3), (-FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0, -FRAC_1_SQRT_3),
+    (FRAC_1_SQRT_3, FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0), (FRAC_1_SQRT_3, FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0),
+    (FRAC_1_SQRT_3, -FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0), (FRAC_1_SQRT_3, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0),
+    (-FRAC_1_SQRT_3, FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0), (-FRAC_1_SQRT_3, FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0),
+    (-FRAC_1_SQRT_3, -FRAC_1_SQRT_3, FRAC_1_SQRT_3, 0.0), (-FRAC_1_SQRT_3, -FRAC_1_SQRT_3, -FRAC_1_SQRT_3, 0.0),
+];
+
+/// Produces four-dimensional gradient-noise using a lattice hash.
+///
+/// Four-dimensional noise is useful to animate three-dimensional noise over time or to
+/// generate seamlessly tiling two-dimensional noise by sampling along a torus. The hash picks
+/// one of 32 gradients pointing to the edges of a tesseract.
+pub struct InterpolatedNoise4D<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        InterpolatedNoise4D<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> InterpolatedNoise4D<H, I> {
+        InterpolatedNoise4D{
+            hash,
+            interpolator
+        }
+    }
+
+    fn gradient(&self, x: i64, y: i64, z: i64, w: i64) -> (f64, f64, f64, f64) {
+        GRADIENTS_4D[select_hash(self.hash.hash4(x, y, z, w), GRADIENTS_4D.len())]
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<(F, F, F, F)>
+        for InterpolatedNoise4D<H, I> {
+
+    type Out = F;
+
+    fn value(&self, (pos_x, pos_y, pos_z, pos_w): (F, F, F, F)) -> F {
+
+        fn dot<F: Float>((dx, dy, dz, dw): (F, F, F, F), (gx, gy, gz, gw): (f64, f64, f64, f64)) -> F {
+            dx * cast(gx) + dy * cast(gy) + dz * cast(gz) + dw * cast(gw)
+        }
+
+        let x0 = floor_to_i64(pos_x);
+        let x1 = x0.wrapping_add(1);
+        let y0 = floor_to_i64(pos_y);
+        let y1 = y0.wrapping_add(1);
+        let z0 = floor_to_i64(pos_z);
+        let z1 = z0.wrapping_add(1);
+        let w0 = floor_to_i64(pos_w);
+        let w1 = w0.wrapping_add(1);
+
+        let px = sawtooth(pos_x);
+        let py = sawtooth(pos_y);
+        let pz = sawtooth(pos_z);
+        let pw = sawtooth(pos_w);
+        let qx = px - F::one();
+        let qy = py - F::one();
+        let qz = pz - F::one();
+        let qw = pw - F::one();
+
+        // nXYZW for X,Y,Z,W <- {0,1}
+        let n0000 = dot((px, py, pz, pw), self.gradient(x0, y0, z0, w0));
+        let n0001 = dot((px, py, pz, qw), self.gradient(x0, y0, z0, w1));
+        let n0010 = dot((px, py, qz, pw), self.gradient(x0, y0, z1, w0));
+        let n0011 = dot((px, py, qz, qw), self.gradient(x0, y0, z1, w1));
+        let n0100 = dot((px, qy, pz, pw), self.gradient(x0, y1, z0, w0));
+        let n0101 = dot((px, qy, pz, qw), self.gradient(x0, y1, z0, w1));
+        let n0110 = dot((px, qy, qz, pw), self.gradient(x0, y1, z1, w0));
+        let n0111 = dot((px, qy, qz, qw), self.gradient(x0, y1, z1, w1));
+        let n1000 = dot((qx, py, pz, pw), self.gradient(x1, y0, z0, w0));
+        let n1001 = dot((qx, py, pz, qw), self.gradient(x1, y0, z0, w1));
+        let n1010 = dot((qx, py, qz, pw), self.gradient(x1, y0, z1, w0));
+        let n1011 = dot((qx, py, qz, qw), self.gradient(x1, y0, z1, w1));
+        let n1100 = dot((qx, qy, pz, pw), self.gradient(x1, y1, z0, w0));
+        let n1101 = dot((qx, qy, pz, qw), self.gradient(x1, y1, z0, w1));
+        let n1110 = dot((qx, qy, qz, pw), self.gradient(x1, y1, z1, w0));
+        let n1111 = dot((qx, qy, qz, qw), self.gradient(x1, y1, z1, w1));
+
+        // interpolate all eight x directions
+        let val000 = self.interpolator.interpolate(n0000, n1000, px);
+        let val001 = self.interpolator.interpolate(n0001, n1001, px);
+        let val010 = self.interpolator.interpolate(n0010, n1010, px);
+        let val011 = self.interpolator.interpolate(n0011, n1011, px);
+        let val100 = self.interpolator.interpolate(n0100, n1100, px);
+        let val101 = self.interpolator.interpolate(n0101, n1101, px);
+        let val110 = self.interpolator.interpolate(n0110, n1110, px);
+        let val111 = self.interpolator.interpolate(n0111, n1111, px);
+
+        // interpolate all four y directions
+        let val00 = self.interpolator.interpolate(val000, val100, py);
+        let val01 = self.interpolator.interpolate(val001, val101, py);
+        let val10 = self.interpolator.interpolate(val010, val110, py);
+        let val11 = self.interpolator.interpolate(val011, val111, py);
+
+        // interpolate both z directions
+        let val0 = self.interpolator.interpolate(val00, val10, pz);
+        let val1 = self.interpolator.interpolate(val01, val11, pz);
+
+        // interpolate in w direction
+        self.interpolator.interpolate(val0, val1, pw)
+    }
+}
+
+// Beyond this the `2^n` lattice corners take too long and too much memory.
+static MAX_DIMENSIONS: usize = 16;
+
+/// Produces gradient-noise of any dimension using a lattice hash.
+///
+/// The dimension is given at runtime by the length of the position. In two, three and four
+/// dimensions the result is the same as the result of `InterpolatedNoise2D::new`,
+/// `InterpolatedNoise3D::new` and `InterpolatedNoise4D::new`, but those are a lot faster. In
+/// more dimensions the gradients are random directions instead of a fixed table. The runtime
+/// grows exponentially with the number of dimensions (`2^n` lattice corners have to be
+/// evaluated). A position without any coordinates is a lattice point and gives zero.
+///
+/// Panics if the position has more than 16 coordinates.
+pub struct InterpolatedNoiseND<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        InterpolatedNoiseND<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> InterpolatedNoiseND<H, I> {
+        InterpolatedNoiseND{
+            hash,
+            interpolator
+        }
+    }
+
+    fn gradient(&self, lattice: &[i64]) -> Vec<f64> {
+        match *lattice {
+            // the only unit vectors in one dimension
+            [x] => vec![[1.0, -1.0][select_hash(self.hash.hash1(x), 2)]],
+            [x, y] => {
+                let (gx, gy) = gradient_table(DEFAULT_GRADIENT_COUNT)[
+                    select_hash(self.hash.hash2(x, y), DEFAULT_GRADIENT_COUNT)];
+                vec![gx, gy]
+            },
+            [x, y, z] => {
+                let (gx, gy, gz) = GRADIENTS_3D[select_hash(self.hash.hash3(x, y, z), GRADIENTS_3D.len())];
+                vec![gx, gy, gz]
+            },
+            [x, y, z, w] => {
+                let (gx, gy, gz, gw) = GRADIENTS_4D[select_hash(self.hash.hash4(x, y, z, w), GRADIENTS_4D.len())];
+                vec![gx, gy, gz, gw]
+            },
+            _ => {
+                // There is no table of evenly spread gradients in higher dimensions, instead
+                // normally distributed components (Box-Muller transform) are generated and
+                // normalized.
+                let mut base: i32 = 0;
+                for (axis, &l) in lattice.iter().enumerate() {
+                    base = base.wrapping_add(fold(l).wrapping_mul(axis_prime(axis)));
+                }
+                let grad: Vec<f64> = (0..lattice.len()).map(|axis| {
+                    let index = base.wrapping_add((axis as i32).wrapping_mul(15268783));
+                    let u = (unit(self.hash.hash1(index as i64)) + 1.0) / 2.0;
+                    let v = unit(self.hash.hash1(index.wrapping_add(23298173) as i64));
+                    (-2.0 * u.ln()).sqrt() * (v * PI).cos()
+                }).collect();
+                let dist = grad.iter().fold(0.0, |acc, g| acc + g.powi(2));
+                if dist == 0.0 {
+                    return grad;
+                }
+                let norm = dist.sqrt();
+                grad.iter().map(|g| g / norm).collect()
+            },
+        }
+    }
+}
+
+/// Returns the prime used to hash the lattice coordinate of the given axis.
+fn axis_prime(axis: usize) -> i32 {
+    let primes = [X_PRIME, Y_PRIME, Z_PRIME, W_PRIME];
+    primes[axis % 4].wrapping_add(((axis / 4) as i32).wrapping_mul(2 * 15268783))
+}
+
+impl<'a, F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<&'a [F]>
+        for InterpolatedNoiseND<H, I> {
+
+    type Out = F;
+
+    fn value(&self, position: &'a [F]) -> F {
+        let dims = position.len();
+        assert!(dims <= MAX_DIMENSIONS, "too many dimensions for the lattice corners to be evaluated");
+        if dims == 0 {
+            return F::zero();
+        }
+        let lower: Vec<i64> = position.iter().map(|p| floor_to_i64(*p)).collect();
+        let percent: Vec<F> = position.iter().map(|p| sawtooth(*p)).collect();
+
+        // the bits of the corner index select the lower or upper lattice point of each axis
+        let mut values: Vec<F> = (0..1 << dims).map(|corner: usize| {
+            let upper: Vec<bool> = (0..dims).map(|axis| (corner >> axis) & 1 == 1).collect();
+            let lattice: Vec<i64> = lower.iter().zip(upper.iter())
+                .map(|(l, u)| if *u { l.wrapping_add(1) } else { *l })
+                .collect();
+            let gradient = self.gradient(&lattice);
+            percent.iter().zip(upper.iter()).zip(gradient.iter())
+                .fold(F::zero(), |acc, ((p, u), g)| {
+                    let offset = if *u { *p - F::one() } else { *p };
+                    acc + offset * cast(*g)
+                })
+        }).collect();
+
+        // interpolate one axis after another, each step halves the number of values
+        for p in percent.iter() {
+            values = values.chunks(2)
+                .map(|pair| self.interpolator.interpolate(pair[0], pair[1], *p))
+                .collect();
+        }
+        values[0]
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<Vec<F>>
+        for InterpolatedNoiseND<H, I> {
+
+    type Out = F;
+
+    fn value(&self, position: Vec<F>) -> F {
+        self.value(&position[..])
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{InterpolatedNoise, InterpolatedNoise2D};
-    use noise::Noise;
-    use default_noise::{NoOpNoise, DefaultI32Noise};
-    use interpolate::LinearInterpolator;
-    use output_op::OutputOp;
-    use std::num::Float;
+    use super::{Gradients, InterpolatedNoise, GradientNoise1D, InterpolatedNoise2D, InterpolatedNoise3D, InterpolatedNoise4D, InterpolatedNoiseND};
+    use crate::noise::Noise;
+    use crate::default_noise::{NoOpNoise, DefaultI32Noise};
+    use crate::hash::{PermutationTable, PcgHash};
+    use crate::interpolate::{LinearInterpolator, PerlinInterpolator};
+    use crate::output_op::OutputOp;
 
     #[test]
     fn interpolated_noise_test() {
@@ -143,6 +570,34 @@ mod test {
         }
     }
 
+    #[test]
+    fn interpolated_noise_i64_test() {
+        let narrow = InterpolatedNoise::<_, _, i32>::new(DefaultI32Noise::new(0), LinearInterpolator);
+        let wide = InterpolatedNoise::<_, _, i64>::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -100..100 {
+            let f = i as f64 / 7.0;
+            assert!(narrow.value(f) == wide.value(f));
+        }
+        for f in [1e12 + 0.5, -1e15 - 0.25, 1e30, -1e300].iter() {
+            let value = wide.value(*f);
+            assert!((-1.0..=1.0).contains(&value));
+            let value = narrow.value(*f);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn gradient_noise_1d_test() {
+        let noise = GradientNoise1D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -1000..1000 {
+            let value = noise.value(i as f64 / 7.0);
+            assert!((-1.0..=1.0).contains(&value));
+            if i % 7 == 0 {
+                assert!(value.abs() < 0.0001);
+            }
+        }
+    }
+
     #[test]
     fn interpolated_noise_2d_test() {
         let noise = InterpolatedNoise2D::new(DefaultI32Noise::new(0), LinearInterpolator);
@@ -151,8 +606,206 @@ mod test {
                 let f = i as f64 / 7.0;
                 let g = j as f64 / 7.0;
                 let value = noise.value((f, g));
-                assert!(-1.0 <= value && value <= 1.0);
+                assert!((-1.0..=1.0).contains(&value));
             }
         }
     }
+
+    #[test]
+    fn interpolated_noise_2d_gradients_test() {
+        for count in [1, 4, 16, 256].iter() {
+            let noise = InterpolatedNoise2D::with_gradients(DefaultI32Noise::new(0), LinearInterpolator,
+                                                            Gradients::Table(*count));
+            for i in -50..50 {
+                for j in -50..50 {
+                    let value = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                    assert!((-1.0..=1.0).contains(&value));
+                    if i % 7 == 0 && j % 7 == 0 {
+                        assert!(value.abs() < 1e-12);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_2d_legacy_test() {
+        // the values of the first version of this crate
+        let noise = InterpolatedNoise2D::with_gradients(DefaultI32Noise::new(0), PerlinInterpolator,
+                                                        Gradients::Legacy);
+        assert!((noise.value((0.3f64, 0.7)) - -0.08498114982095584).abs() < 1e-12);
+        assert!((noise.value((-12.25f64, 4.5)) - 0.21613632192670473).abs() < 1e-12);
+        let noise = InterpolatedNoise2D::with_gradients(DefaultI32Noise::new(7), PerlinInterpolator,
+                                                        Gradients::Legacy);
+        assert!((noise.value((101.9f64, -33.3)) - 0.08529531585668435).abs() < 1e-12);
+        assert!((noise.value((-0.6f64, -1000.2)) - 0.00550313545941053).abs() < 1e-12);
+    }
+
+    #[test]
+    fn interpolated_noise_2d_shared_table_test() {
+        let a = InterpolatedNoise2D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        let b = InterpolatedNoise2D::new(PcgHash::new(1), PerlinInterpolator);
+        assert!(std::ptr::eq(a.gradients, b.gradients));
+        assert!(a.gradients.len() == 256);
+    }
+
+    #[test]
+    #[should_panic]
+    fn interpolated_noise_2d_unsupported_table_test() {
+        InterpolatedNoise2D::with_gradients(DefaultI32Noise::new(0), LinearInterpolator, Gradients::Table(12));
+    }
+
+    #[test]
+    fn interpolated_noise_2d_f32_test() {
+        let noise = InterpolatedNoise2D::new(DefaultI32Noise::new(0), PerlinInterpolator);
+        for i in -50..50 {
+            for j in -50..50 {
+                let single: f32 = noise.value((i as f32 / 7.0, j as f32 / 7.0));
+                let double: f64 = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                assert!(((single as f64) - double).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_2d_hash_test() {
+        for seed in -5..5 {
+            let table = InterpolatedNoise2D::new(PermutationTable::new(seed), LinearInterpolator);
+            let pcg = InterpolatedNoise2D::new(PcgHash::new(seed), LinearInterpolator);
+            for i in -20..20 {
+                for j in -20..20 {
+                    let position = (i as f64 / 3.0, j as f64 / 3.0);
+                    let value = table.value(position);
+                    assert!((-1.0..=1.0).contains(&value));
+                    let value = pcg.value(position);
+                    assert!((-1.0..=1.0).contains(&value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_2d_extreme_test() {
+        let noise = InterpolatedNoise2D::new(DefaultI32Noise::new(i32::MIN), LinearInterpolator);
+        let positions = [-2147483000.5, -1000000000.25, 1000000000.75, 2147483000.5];
+        for x in positions.iter() {
+            for y in positions.iter() {
+                let value = noise.value((*x, *y));
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_2d_huge_coordinates_test() {
+        let noise = InterpolatedNoise2D::new(PcgHash::new(0), LinearInterpolator);
+        let positions = [-1e300, -1e15 - 0.5, -4294967296.25, 4294967296.75, 1e15 + 0.5, 1e300];
+        for x in positions.iter() {
+            for y in positions.iter() {
+                let value = noise.value((*x, *y));
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+        // the lattice does not repeat after 2^32
+        let offset = 4294967296.0;
+        assert!((0..10).any(|i| {
+            let x = i as f64 + 0.5;
+            noise.value((x, 0.5)) != noise.value((x + offset, 0.5))
+        }));
+    }
+
+    #[test]
+    fn interpolated_noise_3d_test() {
+        let noise = InterpolatedNoise3D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -20..20 {
+            for j in -20..20 {
+                for k in -20..20 {
+                    let f = i as f64 / 7.0;
+                    let g = j as f64 / 7.0;
+                    let h = k as f64 / 7.0;
+                    let value = noise.value((f, g, h));
+                    assert!((-1.0..=1.0).contains(&value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_3d_gradients_test() {
+        for (x, y, z) in super::GRADIENTS_3D.iter() {
+            assert!((x * x + y * y + z * z - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_4d_test() {
+        let noise = InterpolatedNoise4D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -8..8 {
+            for j in -8..8 {
+                for k in -8..8 {
+                    for l in -8..8 {
+                        let f = i as f64 / 3.0;
+                        let g = j as f64 / 3.0;
+                        let h = k as f64 / 3.0;
+                        let e = l as f64 / 3.0;
+                        let value = noise.value((f, g, h, e));
+                        assert!((-1.0..=1.0).contains(&value));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_4d_gradients_test() {
+        for (x, y, z, w) in super::GRADIENTS_4D.iter() {
+            assert!((x * x + y * y + z * z + w * w - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn interpolated_noise_nd_test() {
+        let noise_2d = InterpolatedNoise2D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        let noise_nd = InterpolatedNoiseND::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -50..50 {
+            for j in -50..50 {
+                let f = i as f64 / 7.0;
+                let g = j as f64 / 7.0;
+                let value = noise_nd.value(vec![f, g]);
+                assert!((value - noise_2d.value((f, g))).abs() < 1e-12);
+            }
+        }
+
+        for i in -20..20 {
+            let f = i as f64 / 7.0;
+            let value = noise_nd.value(vec![f, -f, f * 0.5, 1.0 - f, f * 2.0]);
+            assert!((-1.5..=1.5).contains(&value));
+        }
+
+        assert!(noise_nd.value(Vec::<f64>::new()) == 0.0);
+    }
+
+    #[test]
+    fn interpolated_noise_nd_3d_4d_test() {
+        let noise_3d = InterpolatedNoise3D::new(DefaultI32Noise::new(3), LinearInterpolator);
+        let noise_4d = InterpolatedNoise4D::new(DefaultI32Noise::new(3), LinearInterpolator);
+        let noise_nd = InterpolatedNoiseND::new(DefaultI32Noise::new(3), LinearInterpolator);
+        for i in -10..10 {
+            for j in -10..10 {
+                let f = i as f64 / 3.0;
+                let g = j as f64 / 7.0;
+                let value = noise_nd.value(vec![f, g, f - g]);
+                assert!((value - noise_3d.value((f, g, f - g))).abs() < 1e-12);
+                let value = noise_nd.value(vec![f, g, f - g, g * 2.0]);
+                assert!((value - noise_4d.value((f, g, f - g, g * 2.0))).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn interpolated_noise_nd_too_many_dimensions_test() {
+        let noise = InterpolatedNoiseND::new(DefaultI32Noise::new(0), LinearInterpolator);
+        noise.value(vec![0.5f64; 17]);
+    }
 }
@@ -0,0 +1,137 @@
+//! Helpers shared by all noise generators working on an integer lattice.
+
+use num_traits::Float;
+use crate::float::{floor_to_i32, floor_to_i64};
+
+pub static X_PRIME: i32 = 1073741827;
+pub static Y_PRIME: i32 = 53688275;
+pub static Z_PRIME: i32 = 334214459;
+pub static W_PRIME: i32 = 393342739;
+
+static HIGH_PRIME: i32 = 1431655781;
+
+/// The integer type of lattice coordinates, implemented for `i32` and `i64`.
+pub trait LatticeCoordinate: Copy {
+    /// Returns the lattice coordinate of a position, saturating at the bounds of the type.
+    fn floor<F: Float>(position: F) -> Self;
+
+    /// Returns the next lattice coordinate, wrapping around at the upper bound.
+    fn next(self) -> Self;
+}
+
+impl LatticeCoordinate for i32 {
+    fn floor<F: Float>(position: F) -> i32 {
+        floor_to_i32(position)
+    }
+
+    fn next(self) -> i32 {
+        self.wrapping_add(1)
+    }
+}
+
+impl LatticeCoordinate for i64 {
+    fn floor<F: Float>(position: F) -> i64 {
+        floor_to_i64(position)
+    }
+
+    fn next(self) -> i64 {
+        self.wrapping_add(1)
+    }
+}
+
+/// Folds a 64 bit lattice coordinate into 32 bits.
+///
+/// Coordinates in the range of `i32` are returned unchanged, so the results of the hashes stay
+/// the same for them.
+pub fn fold(x: i64) -> i32 {
+    let low = x as i32;
+    // zero if and only if the coordinate fits into an i32
+    let high = (x.wrapping_sub(low as i64) >> 32) as i32;
+    low ^ high.wrapping_mul(HIGH_PRIME)
+}
+
+/// Combines two lattice coordinates to an index for one-dimensional source noise.
+///
+/// Like all lattice hashing this wraps around on overflow, in debug and release builds alike.
+pub fn index_2d(x: i32, y: i32) -> i32 {
+    x.wrapping_mul(X_PRIME).wrapping_add(y.wrapping_mul(Y_PRIME))
+}
+
+/// Combines three lattice coordinates to an index for one-dimensional source noise.
+pub fn index_3d(x: i32, y: i32, z: i32) -> i32 {
+    index_2d(x, y).wrapping_add(z.wrapping_mul(Z_PRIME))
+}
+
+/// Combines four lattice coordinates to an index for one-dimensional source noise.
+pub fn index_4d(x: i32, y: i32, z: i32, w: i32) -> i32 {
+    index_3d(x, y, z).wrapping_add(w.wrapping_mul(W_PRIME))
+}
+
+/// Maps a lattice hash to a value between -1 (exclusive) and 1 (inclusive).
+pub fn unit(hash: u32) -> f64 {
+    1.0 - (hash as f64) / 2147483648.0
+}
+
+/// Maps a lattice hash to an index from `0` to `count - 1` using its most significant bits.
+pub fn select_hash(hash: u32, count: usize) -> usize {
+    ((hash as u64 * count as u64) >> 32) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::{select_hash, unit, fold, index_2d, index_3d, index_4d, LatticeCoordinate};
+
+    #[test]
+    fn select_hash_test() {
+        assert!(select_hash(0, 12) == 0);
+        assert!(select_hash(1 << 31, 12) == 6);
+        assert!(select_hash(!0, 12) == 11);
+        assert!(select_hash(!0, 1) == 0);
+    }
+
+    #[test]
+    fn unit_test() {
+        assert!(unit(0) == 1.0);
+        assert!(unit(1 << 31) == 0.0);
+        assert!(unit(!0) > -1.0);
+    }
+
+    #[test]
+    fn fold_test() {
+        let coords: [i32; 5] = [0, 1, -1, i32::MIN, i32::MAX];
+        for x in coords.iter() {
+            assert!(fold(*x as i64) == *x);
+        }
+        assert!(fold(1 << 32) != 0);
+        assert!(fold(-1 << 32) != 0);
+        assert!(fold(1 << 32) != fold(-1 << 32));
+        fold(i64::MAX);
+        fold(i64::MIN);
+    }
+
+    #[test]
+    fn lattice_coordinate_test() {
+        let large: i64 = LatticeCoordinate::floor(1e30);
+        assert!(large == i64::MAX);
+        let small: i32 = LatticeCoordinate::floor(-1e30);
+        assert!(small == i32::MIN);
+        let nan: i64 = LatticeCoordinate::floor(f64::NAN);
+        assert!(nan == 0);
+        let negative: i64 = LatticeCoordinate::floor(-3000000000.5);
+        assert!(negative == -3000000001);
+        assert!(large.next() == i64::MIN);
+    }
+
+    #[test]
+    fn index_extreme_test() {
+        let coords: [i32; 5] = [0, 1, -1, i32::MIN, i32::MAX];
+        for x in coords.iter() {
+            for y in coords.iter() {
+                // must not panic on overflow
+                index_2d(*x, *y);
+                index_3d(*x, *y, *x);
+                index_4d(*x, *y, *x, *y);
+            }
+        }
+    }
+}
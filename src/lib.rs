@@ -2,23 +2,42 @@
 //!
 //! The `examples` folder contains several examples that show how to use this library.
 
-#![feature(core, test)]
-
-extern crate test;
-
 pub use noise::Noise;
 pub use default_noise::{DefaultI32Noise, ConstantNoise, NoOpNoise, UnboxNoise};
-pub use interpolated_noise::{InterpolatedNoise, InterpolatedNoise2D};
+pub use interpolated_noise::{InterpolatedNoise, InterpolatedNoise2D, InterpolatedNoise3D, InterpolatedNoise4D,
+                             InterpolatedNoiseND, GradientNoise1D, Gradients, DEFAULT_GRADIENT_COUNT};
 pub use output_op::OutputOp;
 pub use input_op::InputOp;
 pub use combined_noise::{CombinedNoise, CombinedNoise2};
+pub use select::{Select, Blend};
+pub use displace::{Displace1D, Displace2D, Displace3D};
+pub use fractal::{Fractal, Fbm, RidgedMulti, Billow, Turbulence, HybridMulti, HeteroTerrain, hash_seed};
+pub use simplex_noise::{SimplexNoise2D, SimplexNoise3D, SimplexNoise4D};
+pub use open_simplex_noise::{OpenSimplexNoise2D, OpenSimplexNoise3D, OpenSimplexNoise4D};
+pub use value_noise::{ValueNoise2D, ValueNoise3D};
+pub use worley_noise::{WorleyNoise2D, WorleyNoise3D, WorleyCell, WorleyDistance, WorleyReturn};
+pub use lattice::LatticeCoordinate;
+pub use noise_ext::{NoiseExt, Position};
+pub use ops::{IntoNoise, AddNoise, SubNoise, MulNoise, DivNoise, NegNoise, MinNoise, MaxNoise};
 
 pub mod interpolate;
+pub mod hash;
 pub mod blocks;
 
 mod noise;
+mod noise_ext;
+mod ops;
 mod default_noise;
 mod interpolated_noise;
 mod output_op;
 mod input_op;
 mod combined_noise;
+mod select;
+mod displace;
+mod fractal;
+mod simplex_noise;
+mod open_simplex_noise;
+mod value_noise;
+mod worley_noise;
+mod lattice;
+mod float;
@@ -1,5 +1,11 @@
+use std::rc::Rc;
+use std::sync::Arc;
 
 /// A common interface for all structs that can generate noise.
+///
+/// References and the smart pointers `Box`, `Rc` and `Arc` to a noise are noise as well,
+/// so one generator can be shared by several building blocks and `Box<dyn Noise<...>>`
+/// can be used as source directly.
 pub trait Noise<In> {
 	type Out;
 
@@ -9,3 +15,62 @@ pub trait Noise<In> {
     fn value(&self, position: In) -> Self::Out;
 }
 
+impl<In, N: Noise<In> + ?Sized> Noise<In> for &N {
+    type Out = N::Out;
+
+    fn value(&self, position: In) -> N::Out {
+        (**self).value(position)
+    }
+}
+
+impl<In, N: Noise<In> + ?Sized> Noise<In> for Box<N> {
+    type Out = N::Out;
+
+    fn value(&self, position: In) -> N::Out {
+        (**self).value(position)
+    }
+}
+
+impl<In, N: Noise<In> + ?Sized> Noise<In> for Rc<N> {
+    type Out = N::Out;
+
+    fn value(&self, position: In) -> N::Out {
+        (**self).value(position)
+    }
+}
+
+impl<In, N: Noise<In> + ?Sized> Noise<In> for Arc<N> {
+    type Out = N::Out;
+
+    fn value(&self, position: In) -> N::Out {
+        (**self).value(position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Noise;
+    use crate::combined_noise::CombinedNoise2;
+    use crate::default_noise::DefaultI32Noise;
+    use crate::output_op::OutputOp;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn shared_source_test() {
+        let source = Rc::new(DefaultI32Noise::new(5));
+        let doubled = OutputOp::new(source.clone(), |f: f64| { f * 2.0 });
+        let sum = CombinedNoise2::new(&doubled, Arc::new(DefaultI32Noise::new(5)), |a: f64, b: f64| { a + b });
+        for i in -100..100 {
+            assert!(doubled.value(i) == source.value(i) * 2.0);
+            assert!(sum.value(i) == source.value(i) * 3.0);
+        }
+    }
+
+    #[test]
+    fn boxed_trait_object_test() {
+        let boxed: Box<dyn Noise<i32, Out=f64>> = Box::new(DefaultI32Noise::new(5));
+        let noise = OutputOp::new(boxed, |f: f64| { -f });
+        assert!(noise.value(17) == -DefaultI32Noise::new(5).value(17i32));
+    }
+}
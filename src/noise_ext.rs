@@ -0,0 +1,200 @@
+use crate::float::cast;
+use crate::fractal::{Fbm, Fractal};
+use crate::input_op::InputOp;
+use crate::noise::Noise;
+use crate::ops::{MaxNoise, MinNoise};
+use crate::output_op::OutputOp;
+use num_traits::Float;
+use std::sync::Arc;
+
+/// A position of a continuous noise function, a single float or a tuple of floats.
+pub trait Position: Copy {
+    /// Creates a position with all coordinates set to `value`.
+    fn splat(value: f64) -> Self;
+
+    /// Multiplies the coordinates with the ones of `factor`.
+    fn scale(self, factor: Self) -> Self;
+
+    /// Adds the coordinates of `offset`.
+    fn translate(self, offset: Self) -> Self;
+}
+
+macro_rules! impl_position {
+    ($($f:ty),*) => {$(
+        impl Position for $f {
+            fn splat(value: f64) -> $f {
+                cast(value)
+            }
+
+            fn scale(self, factor: $f) -> $f {
+                self * factor
+            }
+
+            fn translate(self, offset: $f) -> $f {
+                self + offset
+            }
+        }
+
+        impl Position for ($f, $f) {
+            fn splat(value: f64) -> ($f, $f) {
+                (cast(value), cast(value))
+            }
+
+            fn scale(self, (fx, fy): ($f, $f)) -> ($f, $f) {
+                (self.0 * fx, self.1 * fy)
+            }
+
+            fn translate(self, (ox, oy): ($f, $f)) -> ($f, $f) {
+                (self.0 + ox, self.1 + oy)
+            }
+        }
+
+        impl Position for ($f, $f, $f) {
+            fn splat(value: f64) -> ($f, $f, $f) {
+                (cast(value), cast(value), cast(value))
+            }
+
+            fn scale(self, (fx, fy, fz): ($f, $f, $f)) -> ($f, $f, $f) {
+                (self.0 * fx, self.1 * fy, self.2 * fz)
+            }
+
+            fn translate(self, (ox, oy, oz): ($f, $f, $f)) -> ($f, $f, $f) {
+                (self.0 + ox, self.1 + oy, self.2 + oz)
+            }
+        }
+
+        impl Position for ($f, $f, $f, $f) {
+            fn splat(value: f64) -> ($f, $f, $f, $f) {
+                (cast(value), cast(value), cast(value), cast(value))
+            }
+
+            fn scale(self, (fx, fy, fz, fw): ($f, $f, $f, $f)) -> ($f, $f, $f, $f) {
+                (self.0 * fx, self.1 * fy, self.2 * fz, self.3 * fw)
+            }
+
+            fn translate(self, (ox, oy, oz, ow): ($f, $f, $f, $f)) -> ($f, $f, $f, $f) {
+                (self.0 + ox, self.1 + oy, self.2 + oz, self.3 + ow)
+            }
+        }
+    )*}
+}
+
+impl_position!(f32, f64);
+
+/// Chainable methods to build noise out of other noise, implemented for all noise.
+///
+/// The methods create the same building blocks you would nest by hand, e.g.
+/// `noise.scale_input((0.05, 0.05)).map(|f: f64| { f * 10.0 })` is
+/// `OutputOp::new(InputOp::new(noise, ...), ...)`, so everything stays statically dispatched.
+pub trait NoiseExt<In>: Noise<In> + Sized {
+    /// Multiplies the position with `factor` before it is passed to this noise, this changes
+    /// the frequency.
+    fn scale_input(self, factor: In) -> InputOp<Self, impl Fn(In) -> In> where In: Position {
+        InputOp::new(self, move |p: In| { p.scale(factor) })
+    }
+
+    /// Adds `offset` to the position before it is passed to this noise.
+    fn translate(self, offset: In) -> InputOp<Self, impl Fn(In) -> In> where In: Position {
+        InputOp::new(self, move |p: In| { p.translate(offset) })
+    }
+
+    /// Applies `op` to the values of this noise.
+    fn map<Out, Op: Fn(Self::Out) -> Out>(self, op: Op) -> OutputOp<Self, Op> {
+        OutputOp::new(self, op)
+    }
+
+    /// The smaller value of this noise and `other`.
+    fn min<Other: Noise<In, Out=Self::Out>>(self, other: Other) -> MinNoise<Self, Other>
+            where In: Clone, Self::Out: Float {
+        MinNoise::new(self, other)
+    }
+
+    /// The larger value of this noise and `other`.
+    fn max<Other: Noise<In, Out=Self::Out>>(self, other: Other) -> MaxNoise<Self, Other>
+            where In: Clone, Self::Out: Float {
+        MaxNoise::new(self, other)
+    }
+
+    /// Limits the values of this noise to the range from `min` to `max`.
+    fn clamp(self, min: Self::Out, max: Self::Out) -> OutputOp<Self, impl Fn(Self::Out) -> Self::Out>
+            where Self::Out: Float {
+        assert!(min <= max);
+        OutputOp::new(self, move |f: Self::Out| { f.max(min).min(max) })
+    }
+
+    /// The absolute values of this noise.
+    fn abs(self) -> OutputOp<Self, impl Fn(Self::Out) -> Self::Out> where Self::Out: Float {
+        OutputOp::new(self, |f: Self::Out| { f.abs() })
+    }
+
+    /// Fractal Brownian motion with translated copies of this noise as octaves, use `Fbm::new`
+    /// with a factory seeding a new noise for every octave to get independent octaves.
+    fn fbm(self, fractal: Fractal) -> Fbm<impl Noise<In, Out=Self::Out>>
+            where In: Position, Self::Out: Float {
+        let source = Arc::new(self);
+        Fbm::new(fractal, |seed, freq| {
+            // the fractional part keeps the octaves from sharing the lattice points
+            let offset = (seed as u16) as f64 * 0.618034;
+            source.clone().translate(In::splat(offset)).scale_input(In::splat(freq))
+        })
+    }
+
+    /// Moves this noise to the heap, this way different kinds of noise can be stored in the
+    /// same place.
+    fn boxed<'a>(self) -> Box<dyn Noise<In, Out=Self::Out> + 'a> where Self: 'a {
+        Box::new(self)
+    }
+}
+
+impl<In, N: Noise<In>> NoiseExt<In> for N {}
+
+#[cfg(test)]
+mod test {
+    use super::NoiseExt;
+    use crate::blocks::{new_noise_2d, new_perlin_noise_2d};
+    use crate::default_noise::ConstantNoise;
+    use crate::fractal::Fractal;
+    use crate::noise::Noise;
+
+    #[test]
+    fn chained_ops_test() {
+        let base = new_noise_2d(3, 1.0, 1.0);
+        let noise = (new_noise_2d(3, 1.0, 1.0)
+            .scale_input((0.5, 2.0))
+            .translate((1.0, -1.0))
+            .map(|f: f64| { f * 4.0 }) + ConstantNoise::new(1.0))
+            .clamp(-2.0, 3.0);
+        for i in 0..100 {
+            let (x, y) = (i as f64 * 0.37, i as f64 * -0.21);
+            let expected = (base.value(((x + 1.0) * 0.5, (y - 1.0) * 2.0)) * 4.0 + 1.0).clamp(-2.0, 3.0);
+            assert!(noise.value((x, y)) == expected);
+        }
+    }
+
+    #[test]
+    fn combine_test() {
+        let a = new_perlin_noise_2d(1, 1.0f32, 0.1, 3);
+        let b = new_perlin_noise_2d(2, 1.0f32, 0.1, 3);
+        let min = new_perlin_noise_2d(1, 1.0f32, 0.1, 3).min(new_perlin_noise_2d(2, 1.0f32, 0.1, 3));
+        let max = (&a).max(&b);
+        let product = (new_perlin_noise_2d(1, 1.0f32, 0.1, 3) * new_perlin_noise_2d(2, 1.0f32, 0.1, 3)).abs().boxed();
+        for i in 0..100 {
+            let p = (i as f32 * 1.7, i as f32 * 0.3);
+            assert!(min.value(p) == a.value(p).min(b.value(p)));
+            assert!(max.value(p) == a.value(p).max(b.value(p)));
+            assert!(product.value(p) == (a.value(p) * b.value(p)).abs());
+        }
+    }
+
+    #[test]
+    fn fbm_test() {
+        let noise = new_noise_2d(9, 1.0, 1.0).fbm(Fractal::new(9, 1.0, 0.05, 5));
+        let mut different = false;
+        for i in 0..100 {
+            let value = noise.value((i as f64 * 3.1, i as f64 * 1.9));
+            assert!(value.abs() <= 2.0);
+            different |= value != noise.value((0.0, 0.0));
+        }
+        assert!(different);
+    }
+}
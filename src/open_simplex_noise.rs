@@ -0,0 +1,445 @@
+use crate::noise::Noise;
+use crate::hash::LatticeHash;
+use crate::lattice::select_hash;
+use num_traits::Float;
+use crate::float::{cast, to_f64, floor_to_i64};
+
+// Factors to stretch the input space onto the lattice, (1 / sqrt(n + 1) - 1) / n
+static STRETCH_2D: f64 = -0.211324865405187;
+static STRETCH_3D: f64 = -1.0 / 6.0;
+static STRETCH_4D: f64 = -0.138196601125011;
+
+// Factors to squish the lattice back onto the input space, (sqrt(n + 1) - 1) / n
+static SQUISH_2D: f64 = 0.366025403784439;
+static SQUISH_3D: f64 = 1.0 / 3.0;
+static SQUISH_4D: f64 = 0.309016994374947;
+
+// Factors to scale the result to [-1, 1]
+static NORM_2D: f64 = 47.0;
+static NORM_3D: f64 = 103.0;
+static NORM_4D: f64 = 30.0;
+
+// Gradients pointing to the vertices of an octagon.
+static GRAD2: [(f64, f64); 8] = [
+    (5.0, 2.0), (2.0, 5.0), (-5.0, 2.0), (-2.0, 5.0),
+    (5.0, -2.0), (2.0, -5.0), (-5.0, -2.0), (-2.0, -5.0),
+];
+
+// Gradients pointing to the vertices of a rhombicuboctahedron.
+static GRAD3: [(f64, f64, f64); 24] = [
+    (-11.0, 4.0, 4.0), (-4.0, 11.0, 4.0), (-4.0, 4.0, 11.0),
+    (11.0, 4.0, 4.0), (4.0, 11.0, 4.0), (4.0, 4.0, 11.0),
+    (-11.0, -4.0, 4.0), (-4.0, -11.0, 4.0), (-4.0, -4.0, 11.0),
+    (11.0, -4.0, 4.0), (4.0, -11.0, 4.0), (4.0, -4.0, 11.0),
+    (-11.0, 4.0, -4.0), (-4.0, 11.0, -4.0), (-4.0, 4.0, -11.0),
+    (11.0, 4.0, -4.0), (4.0, 11.0, -4.0), (4.0, 4.0, -11.0),
+    (-11.0, -4.0, -4.0), (-4.0, -11.0, -4.0), (-4.0, -4.0, -11.0),
+    (11.0, -4.0, -4.0), (4.0, -11.0, -4.0), (4.0, -4.0, -11.0),
+];
+
+// Gradients pointing to the vertices of a disprismatotesseractihexadecachoron.
+static GRAD4: [(f64, f64, f64, f64); 64] = [
+    (3.0, 1.0, 1.0, 1.0), (3.0, 1.0, 1.0, -1.0), (3.0, 1.0, -1.0, 1.0), (3.0, 1.0, -1.0, -1.0),
+    (3.0, -1.0, 1.0, 1.0), (3.0, -1.0, 1.0, -1.0), (3.0, -1.0, -1.0, 1.0), (3.0, -1.0, -1.0, -1.0),
+    (-3.0, 1.0, 1.0, 1.0), (-3.0, 1.0, 1.0, -1.0), (-3.0, 1.0, -1.0, 1.0), (-3.0, 1.0, -1.0, -1.0),
+    (-3.0, -1.0, 1.0, 1.0), (-3.0, -1.0, 1.0, -1.0), (-3.0, -1.0, -1.0, 1.0), (-3.0, -1.0, -1.0, -1.0),
+    (1.0, 3.0, 1.0, 1.0), (1.0, 3.0, 1.0, -1.0), (1.0, 3.0, -1.0, 1.0), (1.0, 3.0, -1.0, -1.0),
+    (1.0, -3.0, 1.0, 1.0), (1.0, -3.0, 1.0, -1.0), (1.0, -3.0, -1.0, 1.0), (1.0, -3.0, -1.0, -1.0),
+    (-1.0, 3.0, 1.0, 1.0), (-1.0, 3.0, 1.0, -1.0), (-1.0, 3.0, -1.0, 1.0), (-1.0, 3.0, -1.0, -1.0),
+    (-1.0, -3.0, 1.0, 1.0), (-1.0, -3.0, 1.0, -1.0), (-1.0, -3.0, -1.0, 1.0), (-1.0, -3.0, -1.0, -1.0),
+    (1.0, 1.0, 3.0, 1.0), (1.0, 1.0, 3.0, -1.0), (1.0, 1.0, -3.0, 1.0), (1.0, 1.0, -3.0, -1.0),
+    (1.0, -1.0, 3.0, 1.0), (1.0, -1.0, 3.0, -1.0), (1.0, -1.0, -3.0, 1.0), (1.0, -1.0, -3.0, -1.0),
+    (-1.0, 1.0, 3.0, 1.0), (-1.0, 1.0, 3.0, -1.0), (-1.0, 1.0, -3.0, 1.0), (-1.0, 1.0, -3.0, -1.0),
+    (-1.0, -1.0, 3.0, 1.0), (-1.0, -1.0, 3.0, -1.0), (-1.0, -1.0, -3.0, 1.0), (-1.0, -1.0, -3.0, -1.0),
+    (1.0, 1.0, 1.0, 3.0), (1.0, 1.0, 1.0, -3.0), (1.0, 1.0, -1.0, 3.0), (1.0, 1.0, -1.0, -3.0),
+    (1.0, -1.0, 1.0, 3.0), (1.0, -1.0, 1.0, -3.0), (1.0, -1.0, -1.0, 3.0), (1.0, -1.0, -1.0, -3.0),
+    (-1.0, 1.0, 1.0, 3.0), (-1.0, 1.0, 1.0, -3.0), (-1.0, 1.0, -1.0, 3.0), (-1.0, 1.0, -1.0, -3.0),
+    (-1.0, -1.0, 1.0, 3.0), (-1.0, -1.0, 1.0, -3.0), (-1.0, -1.0, -1.0, 3.0), (-1.0, -1.0, -1.0, -3.0),
+];
+
+// The vertices that can be within the radius of the kernel, relative to the origin of the cell in
+// the stretched space. The cell is split into regions by the sum of the fractional coordinates
+// (one table per slab `k <= sum < k + 1`) and by their order. The tables are given for
+// coordinates in descending order, the other orders are their permutations.
+static VERTICES_2D: [&[[i64; 2]]; 2] = [
+    &[[0, 0], [0, 1], [1, -1], [1, 0], [1, 1]],
+    &[[0, 0], [0, 1], [1, 0], [1, 1], [2, 0]],
+];
+
+static VERTICES_3D: [&[[i64; 3]]; 3] = [
+    &[
+        [0, 0, 0], [0, 0, 1], [0, 1, -1], [0, 1, 0], [1, -1, 0],
+        [1, 0, -1], [1, 0, 0], [1, 0, 1], [1, 1, -1], [1, 1, 0],
+    ],
+    &[
+        [0, 0, 0], [0, 0, 1], [0, 1, 0], [0, 1, 1], [1, 0, 0],
+        [1, 0, 1], [1, 1, -1], [1, 1, 0], [1, 1, 1], [2, 0, 0],
+    ],
+    &[
+        [0, 1, 0], [0, 1, 1], [1, 0, 0], [1, 0, 1], [1, 1, 0],
+        [1, 1, 1], [1, 2, 0], [2, 0, 0], [2, 0, 1], [2, 1, 0],
+    ],
+];
+
+static VERTICES_4D: [&[[i64; 4]]; 4] = [
+    &[
+        [0, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, -1], [0, 0, 1, 0],
+        [0, 1, -1, 0], [0, 1, 0, -1], [0, 1, 0, 0], [1, -1, 0, 0],
+        [1, 0, -1, 0], [1, 0, 0, -1], [1, 0, 0, 0], [1, 0, 0, 1],
+        [1, 0, 1, -1], [1, 0, 1, 0], [1, 1, -1, 0], [1, 1, 0, -1],
+        [1, 1, 0, 0],
+    ],
+    &[
+        [0, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 0, 1, 1],
+        [0, 1, 0, 0], [0, 1, 0, 1], [0, 1, 1, -1], [0, 1, 1, 0],
+        [1, 0, 0, 0], [1, 0, 0, 1], [1, 0, 1, -1], [1, 0, 1, 0],
+        [1, 0, 1, 1], [1, 1, -1, 0], [1, 1, 0, -1], [1, 1, 0, 0],
+        [1, 1, 0, 1], [1, 1, 1, -1], [1, 1, 1, 0], [2, 0, 0, 0],
+    ],
+    &[
+        [0, 0, 1, 0], [0, 0, 1, 1], [0, 1, 0, 0], [0, 1, 0, 1],
+        [0, 1, 1, 0], [0, 1, 1, 1], [1, 0, 0, 0], [1, 0, 0, 1],
+        [1, 0, 1, 0], [1, 0, 1, 1], [1, 1, 0, 0], [1, 1, 0, 1],
+        [1, 1, 1, -1], [1, 1, 1, 0], [1, 1, 1, 1], [1, 2, 0, 0],
+        [2, 0, 0, 0], [2, 0, 0, 1], [2, 0, 1, 0], [2, 1, 0, 0],
+    ],
+    &[
+        [0, 1, 1, 0], [0, 1, 1, 1], [1, 0, 1, 0], [1, 0, 1, 1],
+        [1, 1, 0, 0], [1, 1, 0, 1], [1, 1, 1, 0], [1, 1, 1, 1],
+        [1, 1, 2, 0], [1, 2, 0, 0], [1, 2, 0, 1], [1, 2, 1, 0],
+        [2, 0, 1, 0], [2, 0, 1, 1], [2, 1, 0, 0], [2, 1, 0, 1],
+        [2, 1, 1, 0],
+    ],
+];
+
+// Returns the axes sorted by the descending fractional coordinates and the slab of the region
+// containing the position.
+fn region<const N: usize>(ins: [f64; N]) -> ([usize; N], usize) {
+    let mut axes: [usize; N] = std::array::from_fn(|axis| axis);
+    for i in 1..N {
+        let mut j = i;
+        while j > 0 && ins[axes[j]] > ins[axes[j - 1]] {
+            axes.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+    let sum: f64 = ins.iter().sum();
+    (axes, (sum.max(0.0) as usize).min(N - 1))
+}
+
+// Moves the offsets of a region table from the descending order to the given axes.
+fn permute<const N: usize>(vertex: &[i64; N], axes: &[usize; N]) -> [i64; N] {
+    let mut offset = [0; N];
+    for (axis, o) in axes.iter().zip(vertex.iter()) {
+        offset[*axis] = *o;
+    }
+    offset
+}
+
+/// Produces two-dimensional OpenSimplex noise using a lattice hash.
+///
+/// OpenSimplex noise is a patent-free alternative to simplex noise with similar properties.
+/// Instead of skewing the input space onto a simplex grid it stretches the input space onto a
+/// hypercubic honeycomb and sums the contributions of all lattice vertices within the radius of
+/// the kernel. Which vertices these are only depends on the region of the cell containing the
+/// position, so only those are evaluated. See [the original implementation](
+/// https://gist.github.com/KdotJPG/b1270127455a94ac5d19) for details. The result is between -1
+/// and 1.
+pub struct OpenSimplexNoise2D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> OpenSimplexNoise2D<H> {
+    pub fn new(hash: H) -> OpenSimplexNoise2D<H> {
+        OpenSimplexNoise2D{
+            hash
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F)> for OpenSimplexNoise2D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y): (F, F)) -> F {
+        // evaluated in f64, `f32` positions are converted without loss
+        cast(self.evaluate((to_f64(x), to_f64(y))))
+    }
+}
+
+impl<H: LatticeHash> OpenSimplexNoise2D<H> {
+    fn evaluate(&self, (x, y): (f64, f64)) -> f64 {
+        // stretch the input space onto the lattice to find the cell
+        let stretch = (x + y) * STRETCH_2D;
+        let xs = x + stretch;
+        let ys = y + stretch;
+        let xsb = floor_to_i64(xs);
+        let ysb = floor_to_i64(ys);
+        let (axes, slab) = region([xs - (xsb as f64), ys - (ysb as f64)]);
+
+        // position relative to the origin of the cell in the input space
+        let squish = ((xsb as f64) + (ysb as f64)) * SQUISH_2D;
+        let dx0 = x - (xsb as f64) - squish;
+        let dy0 = y - (ysb as f64) - squish;
+
+        let mut value = 0.0;
+        for vertex in VERTICES_2D[slab] {
+            let [i, j] = permute(vertex, &axes);
+            let squish = ((i + j) as f64) * SQUISH_2D;
+            let dx = dx0 - (i as f64) - squish;
+            let dy = dy0 - (j as f64) - squish;
+            let attn = 2.0 - dx * dx - dy * dy;
+            if attn > 0.0 {
+                let hash = self.hash.hash2(xsb.wrapping_add(i), ysb.wrapping_add(j));
+                let (gx, gy) = GRAD2[select_hash(hash, 8)];
+                value += attn.powi(4) * (gx * dx + gy * dy);
+            }
+        }
+        value / NORM_2D
+    }
+}
+
+/// Produces three-dimensional OpenSimplex noise using a lattice hash.
+///
+/// See `OpenSimplexNoise2D` for details.
+pub struct OpenSimplexNoise3D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> OpenSimplexNoise3D<H> {
+    pub fn new(hash: H) -> OpenSimplexNoise3D<H> {
+        OpenSimplexNoise3D{
+            hash
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F, F)> for OpenSimplexNoise3D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y, z): (F, F, F)) -> F {
+        cast(self.evaluate((to_f64(x), to_f64(y), to_f64(z))))
+    }
+}
+
+impl<H: LatticeHash> OpenSimplexNoise3D<H> {
+    fn evaluate(&self, (x, y, z): (f64, f64, f64)) -> f64 {
+        // stretch the input space onto the lattice to find the cell
+        let stretch = (x + y + z) * STRETCH_3D;
+        let xs = x + stretch;
+        let ys = y + stretch;
+        let zs = z + stretch;
+        let xsb = floor_to_i64(xs);
+        let ysb = floor_to_i64(ys);
+        let zsb = floor_to_i64(zs);
+        let (axes, slab) = region([xs - (xsb as f64), ys - (ysb as f64), zs - (zsb as f64)]);
+
+        // position relative to the origin of the cell in the input space
+        let squish = ((xsb as f64) + (ysb as f64) + (zsb as f64)) * SQUISH_3D;
+        let dx0 = x - (xsb as f64) - squish;
+        let dy0 = y - (ysb as f64) - squish;
+        let dz0 = z - (zsb as f64) - squish;
+
+        let mut value = 0.0;
+        for vertex in VERTICES_3D[slab] {
+            let [i, j, k] = permute(vertex, &axes);
+            let squish = ((i + j + k) as f64) * SQUISH_3D;
+            let dx = dx0 - (i as f64) - squish;
+            let dy = dy0 - (j as f64) - squish;
+            let dz = dz0 - (k as f64) - squish;
+            let attn = 2.0 - dx * dx - dy * dy - dz * dz;
+            if attn > 0.0 {
+                let hash = self.hash.hash3(xsb.wrapping_add(i), ysb.wrapping_add(j), zsb.wrapping_add(k));
+                let (gx, gy, gz) = GRAD3[select_hash(hash, 24)];
+                value += attn.powi(4) * (gx * dx + gy * dy + gz * dz);
+            }
+        }
+        value / NORM_3D
+    }
+}
+
+/// Produces four-dimensional OpenSimplex noise using a lattice hash.
+///
+/// See `OpenSimplexNoise2D` for details.
+pub struct OpenSimplexNoise4D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> OpenSimplexNoise4D<H> {
+    pub fn new(hash: H) -> OpenSimplexNoise4D<H> {
+        OpenSimplexNoise4D{
+            hash
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F, F, F)> for OpenSimplexNoise4D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y, z, w): (F, F, F, F)) -> F {
+        cast(self.evaluate((to_f64(x), to_f64(y), to_f64(z), to_f64(w))))
+    }
+}
+
+impl<H: LatticeHash> OpenSimplexNoise4D<H> {
+    fn evaluate(&self, (x, y, z, w): (f64, f64, f64, f64)) -> f64 {
+        // stretch the input space onto the lattice to find the cell
+        let stretch = (x + y + z + w) * STRETCH_4D;
+        let xs = x + stretch;
+        let ys = y + stretch;
+        let zs = z + stretch;
+        let ws = w + stretch;
+        let xsb = floor_to_i64(xs);
+        let ysb = floor_to_i64(ys);
+        let zsb = floor_to_i64(zs);
+        let wsb = floor_to_i64(ws);
+        let (axes, slab) = region([xs - (xsb as f64), ys - (ysb as f64), zs - (zsb as f64), ws - (wsb as f64)]);
+
+        // position relative to the origin of the cell in the input space
+        let squish = ((xsb as f64) + (ysb as f64) + (zsb as f64) + (wsb as f64)) * SQUISH_4D;
+        let dx0 = x - (xsb as f64) - squish;
+        let dy0 = y - (ysb as f64) - squish;
+        let dz0 = z - (zsb as f64) - squish;
+        let dw0 = w - (wsb as f64) - squish;
+
+        let mut value = 0.0;
+        for vertex in VERTICES_4D[slab] {
+            let [i, j, k, l] = permute(vertex, &axes);
+            let squish = ((i + j + k + l) as f64) * SQUISH_4D;
+            let dx = dx0 - (i as f64) - squish;
+            let dy = dy0 - (j as f64) - squish;
+            let dz = dz0 - (k as f64) - squish;
+            let dw = dw0 - (l as f64) - squish;
+            let attn = 2.0 - dx * dx - dy * dy - dz * dz - dw * dw;
+            if attn > 0.0 {
+                let hash = self.hash.hash4(xsb.wrapping_add(i), ysb.wrapping_add(j),
+                                           zsb.wrapping_add(k), wsb.wrapping_add(l));
+                let (gx, gy, gz, gw) = GRAD4[select_hash(hash, 64)];
+                value += attn.powi(4) * (gx * dx + gy * dy + gz * dz + gw * dw);
+            }
+        }
+        value / NORM_4D
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{OpenSimplexNoise2D, OpenSimplexNoise3D, OpenSimplexNoise4D, GRAD2, GRAD3, GRAD4, NORM_2D, NORM_3D,
+                NORM_4D, SQUISH_2D, SQUISH_3D, SQUISH_4D, STRETCH_2D, STRETCH_3D, STRETCH_4D};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::hash::{LatticeHash, PcgHash};
+    use crate::lattice::{select_hash, unit};
+    use crate::noise::Noise;
+
+    // Sums the contributions of all vertices up to one lattice unit around the cell.
+    fn brute_force<const N: usize>(position: [f64; N], (stretch, squish, norm): (f64, f64, f64),
+                                   gradient_dot: impl Fn([i64; N], [f64; N]) -> f64) -> f64 {
+        let stretch = position.iter().sum::<f64>() * stretch;
+        let cell: [i64; N] = std::array::from_fn(|axis| (position[axis] + stretch).floor() as i64);
+        let mut value = 0.0;
+        for index in 0..4usize.pow(N as u32) {
+            let vertex: [i64; N] = std::array::from_fn(|axis| {
+                cell[axis] + (index / 4usize.pow(axis as u32) % 4) as i64 - 1
+            });
+            let squish = vertex.iter().sum::<i64>() as f64 * squish;
+            let d: [f64; N] = std::array::from_fn(|axis| position[axis] - vertex[axis] as f64 - squish);
+            let attn = 2.0 - d.iter().map(|c| c * c).sum::<f64>();
+            if attn > 0.0 {
+                value += attn.powi(4) * gradient_dot(vertex, d);
+            }
+        }
+        value / norm
+    }
+
+    // Random positions and positions on the borders of the regions.
+    fn positions<const N: usize>(count: i64) -> Vec<[f64; N]> {
+        let random = PcgHash::new(1);
+        let mut positions: Vec<[f64; N]> = (0..count).map(|i| {
+            std::array::from_fn(|axis| unit(random.hash1(i * N as i64 + axis as i64)) * 50.0)
+        }).collect();
+        positions.extend((0..count).map(|i| std::array::from_fn(|axis| ((i >> axis) % 5 - 2) as f64 / 2.0)));
+        positions
+    }
+
+    #[test]
+    fn open_simplex_noise_brute_force_test() {
+        let noise = OpenSimplexNoise2D::new(DefaultI32Noise::new(3));
+        for p in positions::<2>(2000) {
+            let expected = brute_force(p, (STRETCH_2D, SQUISH_2D, NORM_2D), |v, d| {
+                let (gx, gy) = GRAD2[select_hash(noise.hash.hash2(v[0], v[1]), 8)];
+                gx * d[0] + gy * d[1]
+            });
+            assert!((noise.value((p[0], p[1])) - expected).abs() < 1e-12);
+        }
+
+        let noise = OpenSimplexNoise3D::new(DefaultI32Noise::new(3));
+        for p in positions::<3>(1000) {
+            let expected = brute_force(p, (STRETCH_3D, SQUISH_3D, NORM_3D), |v, d| {
+                let (gx, gy, gz) = GRAD3[select_hash(noise.hash.hash3(v[0], v[1], v[2]), 24)];
+                gx * d[0] + gy * d[1] + gz * d[2]
+            });
+            assert!((noise.value((p[0], p[1], p[2])) - expected).abs() < 1e-12);
+        }
+
+        let noise = OpenSimplexNoise4D::new(DefaultI32Noise::new(3));
+        for p in positions::<4>(1000) {
+            let expected = brute_force(p, (STRETCH_4D, SQUISH_4D, NORM_4D), |v, d| {
+                let (gx, gy, gz, gw) = GRAD4[select_hash(noise.hash.hash4(v[0], v[1], v[2], v[3]), 64)];
+                gx * d[0] + gy * d[1] + gz * d[2] + gw * d[3]
+            });
+            assert!((noise.value((p[0], p[1], p[2], p[3])) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn open_simplex_noise_reference_test() {
+        let noise_2d = OpenSimplexNoise2D::new(DefaultI32Noise::new(3));
+        let noise_3d = OpenSimplexNoise3D::new(DefaultI32Noise::new(3));
+        let noise_4d = OpenSimplexNoise4D::new(DefaultI32Noise::new(3));
+        let close = |value: f64, expected: f64| { (value - expected).abs() < 1e-12 };
+        assert!(close(noise_2d.value((-12.25, 4.5)), -0.4166822767711643));
+        assert!(close(noise_2d.value((101.9, -33.3)), 0.6659312096667019));
+        assert!(close(noise_3d.value((0.3, 0.7, -0.2)), 0.20145346929529734));
+        assert!(close(noise_3d.value((101.9, -33.3, 0.01)), -0.0709484002920659));
+        assert!(close(noise_4d.value((0.3, 0.7, -0.2, 0.9)), -0.25691935408080807));
+        assert!(close(noise_4d.value((-12.25, 4.5, 8.125, -3.75)), -0.16246107497290746));
+    }
+
+    #[test]
+    fn open_simplex_noise_2d_test() {
+        let noise = OpenSimplexNoise2D::new(DefaultI32Noise::new(0));
+        for i in -100..100 {
+            for j in -100..100 {
+                let value = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn open_simplex_noise_3d_test() {
+        let noise = OpenSimplexNoise3D::new(DefaultI32Noise::new(0));
+        for i in -20..20 {
+            for j in -20..20 {
+                for k in -20..20 {
+                    let value = noise.value((i as f64 / 7.0, j as f64 / 7.0, k as f64 / 7.0));
+                    assert!((-1.0..=1.0).contains(&value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn open_simplex_noise_4d_test() {
+        let noise = OpenSimplexNoise4D::new(DefaultI32Noise::new(0));
+        for i in -8..8 {
+            for j in -8..8 {
+                for k in -8..8 {
+                    for l in -8..8 {
+                        let value = noise.value((i as f64 / 3.0, j as f64 / 3.0, k as f64 / 3.0, l as f64 / 3.0));
+                        assert!((-1.0..=1.0).contains(&value));
+                    }
+                }
+            }
+        }
+    }
+}
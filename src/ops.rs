@@ -0,0 +1,322 @@
+//! Arithmetic operators on noise.
+//!
+//! All noise types of this crate support `+`, `-`, `*` and `/` with other noise or with a
+//! constant `f32`/`f64` on the right hand side, and unary `-`. The results are noise again, so
+//! `(a + b * 0.5).abs()` works as expected. A constant on the left hand side has to be an `f64`,
+//! this way the type of a literal like in `0.5 * a` is known.
+
+use crate::combined_noise::{CombinedNoise, CombinedNoise2};
+use crate::default_noise::{ConstantNoise, DefaultI32Noise, NoOpNoise, UnboxNoise};
+use crate::displace::{Displace1D, Displace2D, Displace3D};
+use crate::fractal::{Billow, Fbm, HeteroTerrain, HybridMulti, RidgedMulti, Turbulence};
+use crate::hash::LatticeHash;
+use crate::input_op::InputOp;
+use crate::interpolated_noise::{GradientNoise1D, InterpolatedNoise, InterpolatedNoise2D, InterpolatedNoise3D,
+                                InterpolatedNoise4D, InterpolatedNoiseND};
+use crate::noise::Noise;
+use crate::open_simplex_noise::{OpenSimplexNoise2D, OpenSimplexNoise3D, OpenSimplexNoise4D};
+use crate::output_op::OutputOp;
+use crate::select::{Blend, Select};
+use crate::simplex_noise::{SimplexNoise2D, SimplexNoise3D, SimplexNoise4D};
+use crate::value_noise::{ValueNoise2D, ValueNoise3D};
+use crate::worley_noise::{WorleyNoise2D, WorleyNoise3D};
+use num_traits::Float;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The right hand side of an arithmetic operator on noise.
+///
+/// Noise is used as it is, constants are turned into `ConstantNoise`.
+pub trait IntoNoise {
+    type Noise;
+
+    fn into_noise(self) -> Self::Noise;
+}
+
+impl IntoNoise for f32 {
+    type Noise = ConstantNoise<f32>;
+
+    fn into_noise(self) -> ConstantNoise<f32> {
+        ConstantNoise::new(self)
+    }
+}
+
+impl IntoNoise for f64 {
+    type Noise = ConstantNoise<f64>;
+
+    fn into_noise(self) -> ConstantNoise<f64> {
+        ConstantNoise::new(self)
+    }
+}
+
+impl<'a, N: ?Sized> IntoNoise for &'a N {
+    type Noise = &'a N;
+
+    fn into_noise(self) -> &'a N {
+        self
+    }
+}
+
+impl<N: ?Sized> IntoNoise for Box<N> {
+    type Noise = Box<N>;
+
+    fn into_noise(self) -> Box<N> {
+        self
+    }
+}
+
+impl<N: ?Sized> IntoNoise for Rc<N> {
+    type Noise = Rc<N>;
+
+    fn into_noise(self) -> Rc<N> {
+        self
+    }
+}
+
+impl<N: ?Sized> IntoNoise for Arc<N> {
+    type Noise = Arc<N>;
+
+    fn into_noise(self) -> Arc<N> {
+        self
+    }
+}
+
+macro_rules! binary_noise {
+    ($(#[$attr:meta])* $name:ident, [$($bound:tt)*], |$a:ident, $b:ident| $result:expr) => {
+        $(#[$attr])*
+        pub struct $name<Src1, Src2> {
+            source1: Src1,
+            source2: Src2,
+        }
+
+        impl<Src1, Src2> $name<Src1, Src2> {
+            pub fn new(source1: Src1, source2: Src2) -> $name<Src1, Src2> {
+                $name{
+                    source1,
+                    source2
+                }
+            }
+        }
+
+        impl<In: Clone, T: $($bound)*, Src1: Noise<In, Out=T>, Src2: Noise<In, Out=T>> Noise<In>
+                for $name<Src1, Src2> {
+            type Out = T;
+
+            fn value(&self, position: In) -> T {
+                let $a = self.source1.value(position.clone());
+                let $b = self.source2.value(position);
+                $result
+            }
+        }
+    }
+}
+
+binary_noise!(
+    /// The sum of two noises, the result of `a + b`.
+    AddNoise, [Add<Output=T>], |a, b| a + b);
+binary_noise!(
+    /// The difference of two noises, the result of `a - b`.
+    SubNoise, [Sub<Output=T>], |a, b| a - b);
+binary_noise!(
+    /// The product of two noises, the result of `a * b`.
+    MulNoise, [Mul<Output=T>], |a, b| a * b);
+binary_noise!(
+    /// The quotient of two noises, the result of `a / b`.
+    DivNoise, [Div<Output=T>], |a, b| a / b);
+binary_noise!(
+    /// The smaller value of two noises.
+    MinNoise, [Float], |a, b| a.min(b));
+binary_noise!(
+    /// The larger value of two noises.
+    MaxNoise, [Float], |a, b| a.max(b));
+
+/// The negated value of a noise, the result of `-a`.
+pub struct NegNoise<Src> {
+    source: Src,
+}
+
+impl<Src> NegNoise<Src> {
+    pub fn new(source: Src) -> NegNoise<Src> {
+        NegNoise{
+            source
+        }
+    }
+}
+
+impl<In, T: Neg<Output=T>, Src: Noise<In, Out=T>> Noise<In> for NegNoise<Src> {
+    type Out = T;
+
+    fn value(&self, position: In) -> T {
+        -self.source.value(position)
+    }
+}
+
+macro_rules! scalar_ops {
+    ($scalar:ty, [$($generics:tt)*], $noise:ty) => {
+        impl<$($generics)*> Add<$noise> for $scalar {
+            type Output = AddNoise<ConstantNoise<$scalar>, $noise>;
+
+            fn add(self, rhs: $noise) -> Self::Output {
+                AddNoise::new(ConstantNoise::new(self), rhs)
+            }
+        }
+
+        impl<$($generics)*> Sub<$noise> for $scalar {
+            type Output = SubNoise<ConstantNoise<$scalar>, $noise>;
+
+            fn sub(self, rhs: $noise) -> Self::Output {
+                SubNoise::new(ConstantNoise::new(self), rhs)
+            }
+        }
+
+        impl<$($generics)*> Mul<$noise> for $scalar {
+            type Output = MulNoise<ConstantNoise<$scalar>, $noise>;
+
+            fn mul(self, rhs: $noise) -> Self::Output {
+                MulNoise::new(ConstantNoise::new(self), rhs)
+            }
+        }
+
+        impl<$($generics)*> Div<$noise> for $scalar {
+            type Output = DivNoise<ConstantNoise<$scalar>, $noise>;
+
+            fn div(self, rhs: $noise) -> Self::Output {
+                DivNoise::new(ConstantNoise::new(self), rhs)
+            }
+        }
+    }
+}
+
+macro_rules! noise_ops {
+    ($($name:ident<$($lt:lifetime,)* $($param:ident $(: $bound:path)?),*>;)*) => {$(
+        impl<$($lt,)* $($param $(: $bound)?,)*> IntoNoise for $name<$($lt,)* $($param),*> {
+            type Noise = Self;
+
+            fn into_noise(self) -> Self {
+                self
+            }
+        }
+
+        impl<$($lt,)* $($param $(: $bound)?,)* Rhs: IntoNoise> Add<Rhs> for $name<$($lt,)* $($param),*> {
+            type Output = AddNoise<Self, Rhs::Noise>;
+
+            fn add(self, rhs: Rhs) -> Self::Output {
+                AddNoise::new(self, rhs.into_noise())
+            }
+        }
+
+        impl<$($lt,)* $($param $(: $bound)?,)* Rhs: IntoNoise> Sub<Rhs> for $name<$($lt,)* $($param),*> {
+            type Output = SubNoise<Self, Rhs::Noise>;
+
+            fn sub(self, rhs: Rhs) -> Self::Output {
+                SubNoise::new(self, rhs.into_noise())
+            }
+        }
+
+        impl<$($lt,)* $($param $(: $bound)?,)* Rhs: IntoNoise> Mul<Rhs> for $name<$($lt,)* $($param),*> {
+            type Output = MulNoise<Self, Rhs::Noise>;
+
+            fn mul(self, rhs: Rhs) -> Self::Output {
+                MulNoise::new(self, rhs.into_noise())
+            }
+        }
+
+        impl<$($lt,)* $($param $(: $bound)?,)* Rhs: IntoNoise> Div<Rhs> for $name<$($lt,)* $($param),*> {
+            type Output = DivNoise<Self, Rhs::Noise>;
+
+            fn div(self, rhs: Rhs) -> Self::Output {
+                DivNoise::new(self, rhs.into_noise())
+            }
+        }
+
+        impl<$($lt,)* $($param $(: $bound)?,)*> Neg for $name<$($lt,)* $($param),*> {
+            type Output = NegNoise<Self>;
+
+            fn neg(self) -> NegNoise<Self> {
+                NegNoise::new(self)
+            }
+        }
+
+        scalar_ops!(f64, [$($lt,)* $($param $(: $bound)?,)*], $name<$($lt,)* $($param),*>);
+    )*}
+}
+
+noise_ops! {
+    AddNoise<Src1, Src2>;
+    SubNoise<Src1, Src2>;
+    MulNoise<Src1, Src2>;
+    DivNoise<Src1, Src2>;
+    MinNoise<Src1, Src2>;
+    MaxNoise<Src1, Src2>;
+    NegNoise<Src>;
+    DefaultI32Noise<>;
+    ConstantNoise<Out>;
+    NoOpNoise<>;
+    UnboxNoise<'a, In, Out>;
+    InputOp<Src, Op>;
+    OutputOp<Src, Op>;
+    CombinedNoise<'a, In, Out, Combine>;
+    CombinedNoise2<Src1, Src2, Combine>;
+    Select<Src1, Src2, Control, I>;
+    Blend<Src1, Src2, Control, I>;
+    Displace1D<Src, X>;
+    Displace2D<Src, X, Y>;
+    Displace3D<Src, X, Y, Z>;
+    Fbm<Src>;
+    RidgedMulti<Src>;
+    Billow<Src>;
+    Turbulence<Src>;
+    HybridMulti<Src>;
+    HeteroTerrain<Src>;
+    InterpolatedNoise<Src, I, L>;
+    GradientNoise1D<H: LatticeHash, I>;
+    InterpolatedNoise2D<H: LatticeHash, I>;
+    InterpolatedNoise3D<H: LatticeHash, I>;
+    InterpolatedNoise4D<H: LatticeHash, I>;
+    InterpolatedNoiseND<H: LatticeHash, I>;
+    SimplexNoise2D<H: LatticeHash>;
+    SimplexNoise3D<H: LatticeHash>;
+    SimplexNoise4D<H: LatticeHash>;
+    OpenSimplexNoise2D<H: LatticeHash>;
+    OpenSimplexNoise3D<H: LatticeHash>;
+    OpenSimplexNoise4D<H: LatticeHash>;
+    ValueNoise2D<H: LatticeHash, I>;
+    ValueNoise3D<H: LatticeHash, I>;
+    WorleyNoise2D<H: LatticeHash>;
+    WorleyNoise3D<H: LatticeHash>;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocks::{new_noise_2d, new_simplex_noise_2d};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::noise::Noise;
+    use crate::noise_ext::NoiseExt;
+
+    #[test]
+    fn noise_with_noise_test() {
+        let a = new_noise_2d(1, 1.0, 0.1);
+        let b = new_simplex_noise_2d(2, 1.0, 0.1);
+        let noise = (new_noise_2d(1, 1.0, 0.1) + new_simplex_noise_2d(2, 1.0, 0.1) * 0.5).abs();
+        let quotient = new_noise_2d(1, 1.0, 0.1) / &b - &a;
+        for i in 1..100 {
+            let p = (i as f64 * 0.9, i as f64 * -0.4);
+            assert!(noise.value(p) == (a.value(p) + b.value(p) * 0.5).abs());
+            assert!(quotient.value(p) == a.value(p) / b.value(p) - a.value(p));
+        }
+    }
+
+    #[test]
+    fn noise_with_scalar_test() {
+        let noise = -(DefaultI32Noise::new(4) * 2.0 - 1.0);
+        let left = 1.0 - 2.0 * DefaultI32Noise::new(4) + 3.0 / (DefaultI32Noise::new(5) + 4.0);
+        let min = NoiseExt::<i32>::min(DefaultI32Noise::new(4) / 2.0, DefaultI32Noise::new(5)) + 1.0;
+        for i in -100..100i32 {
+            let value: f64 = DefaultI32Noise::new(4).value(i);
+            assert!(noise.value(i) == -(value * 2.0 - 1.0));
+            assert!(left.value(i) == 1.0 - 2.0 * value + 3.0 / (DefaultI32Noise::new(5).value(i) + 4.0));
+            assert!(min.value(i) == (value / 2.0).min(DefaultI32Noise::new(5).value(i)) + 1.0);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use noise::Noise;
+use crate::noise::Noise;
 use std::ops::Fn;
 
 /// Applies an operation to the output of the source noise.
@@ -11,8 +11,8 @@ pub struct OutputOp<Src, Op> {
 impl<Src, Op> OutputOp<Src, Op> {
     pub fn new(source: Src, op: Op) -> OutputOp<Src, Op> {
         OutputOp{
-            source: source,
-            op: op
+            source,
+            op
         }
     }
 }
@@ -32,8 +32,8 @@ impl<In, Mid, Out, Src: Noise<In, Out=Mid>, Op: Fn(Mid) -> Out>
 #[cfg(test)]
 mod test {
     use super::OutputOp;
-    use default_noise::NoOpNoise;
-    use noise::Noise;
+    use crate::default_noise::NoOpNoise;
+    use crate::noise::Noise;
 
     #[test]
     fn output_op_test() {
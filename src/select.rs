@@ -0,0 +1,154 @@
+use crate::float::{cast, to_f64};
+use crate::interpolate::{Interpolator, LinearInterpolator};
+use crate::noise::Noise;
+use num_traits::Float;
+
+/// Selects one of two source noises depending on the value of a control noise.
+///
+/// Where the control value is between `lower` and `upper` (both inclusive) the `inside` noise
+/// is returned, everywhere else the `outside` noise. With a falloff the edges are smoothed: In
+/// the range from `lower - falloff` to `lower + falloff` (and the same around `upper`) both
+/// sources are interpolated using the interpolator. The falloff is limited to half the distance
+/// between the bounds.
+pub struct Select<Src1, Src2, Control, I> {
+    outside: Src1,
+    inside: Src2,
+    control: Control,
+    lower: f64,
+    upper: f64,
+    falloff: f64,
+    interpolator: I,
+}
+
+impl<Src1, Src2, Control> Select<Src1, Src2, Control, LinearInterpolator> {
+    /// Creates a selection with hard edges.
+    pub fn new(outside: Src1, inside: Src2, control: Control, (lower, upper): (f64, f64))
+            -> Select<Src1, Src2, Control, LinearInterpolator> {
+        Select::with_falloff(outside, inside, control, (lower, upper), 0.0, LinearInterpolator)
+    }
+}
+
+impl<Src1, Src2, Control, I> Select<Src1, Src2, Control, I> {
+    /// Creates a selection whose edges are smoothed over `falloff` using `interpolator`.
+    pub fn with_falloff(outside: Src1, inside: Src2, control: Control, (lower, upper): (f64, f64), falloff: f64,
+                        interpolator: I) -> Select<Src1, Src2, Control, I> {
+        assert!(lower <= upper);
+        assert!(falloff >= 0.0);
+        Select{
+            outside,
+            inside,
+            control,
+            lower,
+            upper,
+            falloff: falloff.min((upper - lower) / 2.0),
+            interpolator
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src1: Noise<In, Out=F>, Src2: Noise<In, Out=F>, Control: Noise<In, Out=F>,
+     I: Interpolator<F>> Noise<In> for Select<Src1, Src2, Control, I> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let c = to_f64(self.control.value(position.clone()));
+        let falloff = self.falloff;
+        if c < self.lower - falloff || c > self.upper + falloff {
+            self.outside.value(position)
+        } else if c < self.lower + falloff {
+            let percent = (c - (self.lower - falloff)) / (2.0 * falloff);
+            self.interpolator.interpolate(
+                self.outside.value(position.clone()), self.inside.value(position), cast(percent))
+        } else if c <= self.upper - falloff {
+            self.inside.value(position)
+        } else {
+            let percent = (c - (self.upper - falloff)) / (2.0 * falloff);
+            self.interpolator.interpolate(
+                self.inside.value(position.clone()), self.outside.value(position), cast(percent))
+        }
+    }
+}
+
+/// Blends two source noises using a control noise.
+///
+/// A control value of `-1` (or less) gives the first source, `1` (or more) the second one and
+/// the values in between are interpolated using the interpolator.
+pub struct Blend<Src1, Src2, Control, I> {
+    source1: Src1,
+    source2: Src2,
+    control: Control,
+    interpolator: I,
+}
+
+impl<Src1, Src2, Control, I> Blend<Src1, Src2, Control, I> {
+    pub fn new(source1: Src1, source2: Src2, control: Control, interpolator: I) -> Blend<Src1, Src2, Control, I> {
+        Blend{
+            source1,
+            source2,
+            control,
+            interpolator
+        }
+    }
+}
+
+impl<In: Clone, F: Float, Src1: Noise<In, Out=F>, Src2: Noise<In, Out=F>, Control: Noise<In, Out=F>,
+     I: Interpolator<F>> Noise<In> for Blend<Src1, Src2, Control, I> {
+    type Out = F;
+
+    fn value(&self, position: In) -> F {
+        let c = self.control.value(position.clone());
+        let percent = ((c + F::one()) / cast(2.0)).max(F::zero()).min(F::one());
+        self.interpolator.interpolate(
+            self.source1.value(position.clone()), self.source2.value(position), percent)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Blend, Select};
+    use crate::default_noise::{ConstantNoise, NoOpNoise};
+    use crate::interpolate::{LinearInterpolator, PerlinInterpolator};
+    use crate::noise::Noise;
+
+    #[test]
+    fn select_test() {
+        let noise = Select::new(ConstantNoise::new(-5.0), ConstantNoise::new(5.0), NoOpNoise, (-0.5, 0.5));
+        assert!(noise.value(-0.6) == -5.0);
+        assert!(noise.value(-0.5) == 5.0);
+        assert!(noise.value(0.5) == 5.0);
+        assert!(noise.value(0.6) == -5.0);
+    }
+
+    #[test]
+    fn select_falloff_test() {
+        let noise = Select::with_falloff(ConstantNoise::new(0.0), ConstantNoise::new(1.0), NoOpNoise, (0.0, 1.0),
+                                         0.25, PerlinInterpolator);
+        assert!(noise.value(-0.25) == 0.0);
+        assert!(noise.value(0.0) == 0.5);
+        assert!(noise.value(0.25) == 1.0);
+        assert!(noise.value(0.75) == 1.0);
+        assert!(noise.value(1.0) == 0.5);
+        assert!(noise.value(1.25) == 0.0);
+        let mut last = 0.0;
+        for i in -30..=30 {
+            let value = noise.value(i as f64 / 100.0);
+            assert!(last <= value);
+            last = value;
+        }
+        // the falloff is limited to half the range
+        let narrow = Select::with_falloff(ConstantNoise::new(0.0), ConstantNoise::new(1.0), NoOpNoise, (0.0, 0.2),
+                                          1.0, LinearInterpolator);
+        assert!(narrow.value(0.1) == 1.0);
+        assert!(narrow.value(-0.1) == 0.0);
+    }
+
+    #[test]
+    fn blend_test() {
+        let noise = Blend::new(ConstantNoise::new(2.0f32), ConstantNoise::new(4.0), NoOpNoise, LinearInterpolator);
+        assert!(noise.value(-2.0) == 2.0);
+        assert!(noise.value(-1.0) == 2.0);
+        assert!(noise.value(0.0) == 3.0);
+        assert!(noise.value(1.0) == 4.0);
+        assert!(noise.value(3.0) == 4.0);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::noise::Noise;
+use crate::hash::LatticeHash;
+use crate::lattice::select_hash;
+use num_traits::Float;
+use crate::float::{cast, to_f64, floor_to_i64};
+
+// Skewing and unskewing factors, (sqrt(n + 1) - 1) / n and (n + 1 - sqrt(n + 1)) / (n * (n + 1))
+static F2: f64 = 0.36602540378443865;
+static G2: f64 = 0.21132486540518713;
+static F3: f64 = 1.0 / 3.0;
+static G3: f64 = 1.0 / 6.0;
+static F4: f64 = 0.30901699437494745;
+static G4: f64 = 0.1381966011250105;
+
+// The midpoints of the edges of a cube, the first two components are also used for 2D.
+static GRAD3: [(f64, f64, f64); 12] = [
+    (1.0, 1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, -1.0, 0.0),
+    (1.0, 0.0, 1.0), (-1.0, 0.0, 1.0), (1.0, 0.0, -1.0), (-1.0, 0.0, -1.0),
+    (0.0, 1.0, 1.0), (0.0, -1.0, 1.0), (0.0, 1.0, -1.0), (0.0, -1.0, -1.0),
+];
+
+// The midpoints of the edges of a four-dimensional hypercube.
+static GRAD4: [(f64, f64, f64, f64); 32] = [
+    (0.0, 1.0, 1.0, 1.0), (0.0, 1.0, 1.0, -1.0), (0.0, 1.0, -1.0, 1.0), (0.0, 1.0, -1.0, -1.0),
+    (0.0, -1.0, 1.0, 1.0), (0.0, -1.0, 1.0, -1.0), (0.0, -1.0, -1.0, 1.0), (0.0, -1.0, -1.0, -1.0),
+    (1.0, 0.0, 1.0, 1.0), (1.0, 0.0, 1.0, -1.0), (1.0, 0.0, -1.0, 1.0), (1.0, 0.0, -1.0, -1.0),
+    (-1.0, 0.0, 1.0, 1.0), (-1.0, 0.0, 1.0, -1.0), (-1.0, 0.0, -1.0, 1.0), (-1.0, 0.0, -1.0, -1.0),
+    (1.0, 1.0, 0.0, 1.0), (1.0, 1.0, 0.0, -1.0), (1.0, -1.0, 0.0, 1.0), (1.0, -1.0, 0.0, -1.0),
+    (-1.0, 1.0, 0.0, 1.0), (-1.0, 1.0, 0.0, -1.0), (-1.0, -1.0, 0.0, 1.0), (-1.0, -1.0, 0.0, -1.0),
+    (1.0, 1.0, 1.0, 0.0), (1.0, 1.0, -1.0, 0.0), (1.0, -1.0, 1.0, 0.0), (1.0, -1.0, -1.0, 0.0),
+    (-1.0, 1.0, 1.0, 0.0), (-1.0, 1.0, -1.0, 0.0), (-1.0, -1.0, 1.0, 0.0), (-1.0, -1.0, -1.0, 0.0),
+];
+
+/// Produces two-dimensional simplex noise using a lattice hash.
+///
+/// Simplex noise only evaluates the three corners of the triangle containing the position
+/// instead of the four corners of a square and has no visible directional artifacts. See
+/// [Simplex noise demystified](http://webstaff.itn.liu.se/~stegu/simplexnoise/simplexnoise.pdf)
+/// for the implementation. The result is between -1 and 1.
+pub struct SimplexNoise2D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> SimplexNoise2D<H> {
+    pub fn new(hash: H) -> SimplexNoise2D<H> {
+        SimplexNoise2D{
+            hash
+        }
+    }
+
+    fn corner(&self, i: i64, j: i64, x: f64, y: f64) -> f64 {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            0.0
+        } else {
+            let (gx, gy, _) = GRAD3[select_hash(self.hash.hash2(i, j), 12)];
+            t.powi(4) * (gx * x + gy * y)
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F)> for SimplexNoise2D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y): (F, F)) -> F {
+        // evaluated in f64, `f32` positions are converted without loss
+        cast(self.evaluate((to_f64(x), to_f64(y))))
+    }
+}
+
+impl<H: LatticeHash> SimplexNoise2D<H> {
+    fn evaluate(&self, (x, y): (f64, f64)) -> f64 {
+        // skew the input space to find the simplex cell
+        let s = (x + y) * F2;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+
+        // unskew the cell origin back to (x, y) space
+        let t = (i + j) * G2;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+
+        // the middle corner of the triangle
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - (i1 as f64) + G2;
+        let y1 = y0 - (j1 as f64) + G2;
+        let x2 = x0 - 1.0 + 2.0 * G2;
+        let y2 = y0 - 1.0 + 2.0 * G2;
+
+        let i = floor_to_i64(i);
+        let j = floor_to_i64(j);
+        let n0 = self.corner(i, j, x0, y0);
+        let n1 = self.corner(i.wrapping_add(i1), j.wrapping_add(j1), x1, y1);
+        let n2 = self.corner(i.wrapping_add(1), j.wrapping_add(1), x2, y2);
+
+        // scale the result to [-1, 1]
+        70.0 * (n0 + n1 + n2)
+    }
+}
+
+/// Produces three-dimensional simplex noise using a lattice hash.
+///
+/// See `SimplexNoise2D` for details. As in the reference implementation, the corners contribute
+/// slightly beyond the simplices they belong to, so the noise jumps by up to 0.005 between them.
+pub struct SimplexNoise3D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> SimplexNoise3D<H> {
+    pub fn new(hash: H) -> SimplexNoise3D<H> {
+        SimplexNoise3D{
+            hash
+        }
+    }
+
+    fn corner(&self, i: i64, j: i64, k: i64, x: f64, y: f64, z: f64) -> f64 {
+        let t = 0.6 - x * x - y * y - z * z;
+        if t < 0.0 {
+            0.0
+        } else {
+            let (gx, gy, gz) = GRAD3[select_hash(self.hash.hash3(i, j, k), 12)];
+            t.powi(4) * (gx * x + gy * y + gz * z)
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F, F)> for SimplexNoise3D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y, z): (F, F, F)) -> F {
+        cast(self.evaluate((to_f64(x), to_f64(y), to_f64(z))))
+    }
+}
+
+impl<H: LatticeHash> SimplexNoise3D<H> {
+    fn evaluate(&self, (x, y, z): (f64, f64, f64)) -> f64 {
+        // skew the input space to find the simplex cell
+        let s = (x + y + z) * F3;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let k = (z + s).floor();
+
+        // unskew the cell origin back to (x, y, z) space
+        let t = (i + j + k) * G3;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+
+        // the second and third corner of the tetrahedron
+        let ((i1, j1, k1), (i2, j2, k2)) =
+            if x0 >= y0 {
+                if y0 >= z0 {
+                    ((1, 0, 0), (1, 1, 0))
+                } else if x0 >= z0 {
+                    ((1, 0, 0), (1, 0, 1))
+                } else {
+                    ((0, 0, 1), (1, 0, 1))
+                }
+            } else {
+                if y0 < z0 {
+                    ((0, 0, 1), (0, 1, 1))
+                } else if x0 < z0 {
+                    ((0, 1, 0), (0, 1, 1))
+                } else {
+                    ((0, 1, 0), (1, 1, 0))
+                }
+            };
+
+        let x1 = x0 - (i1 as f64) + G3;
+        let y1 = y0 - (j1 as f64) + G3;
+        let z1 = z0 - (k1 as f64) + G3;
+        let x2 = x0 - (i2 as f64) + 2.0 * G3;
+        let y2 = y0 - (j2 as f64) + 2.0 * G3;
+        let z2 = z0 - (k2 as f64) + 2.0 * G3;
+        let x3 = x0 - 1.0 + 3.0 * G3;
+        let y3 = y0 - 1.0 + 3.0 * G3;
+        let z3 = z0 - 1.0 + 3.0 * G3;
+
+        let i = floor_to_i64(i);
+        let j = floor_to_i64(j);
+        let k = floor_to_i64(k);
+        let n0 = self.corner(i, j, k, x0, y0, z0);
+        let n1 = self.corner(i.wrapping_add(i1), j.wrapping_add(j1), k.wrapping_add(k1),
+                             x1, y1, z1);
+        let n2 = self.corner(i.wrapping_add(i2), j.wrapping_add(j2), k.wrapping_add(k2),
+                             x2, y2, z2);
+        let n3 = self.corner(i.wrapping_add(1), j.wrapping_add(1), k.wrapping_add(1),
+                             x3, y3, z3);
+
+        // scale the result to [-1, 1]
+        32.0 * (n0 + n1 + n2 + n3)
+    }
+}
+
+/// Produces four-dimensional simplex noise using a lattice hash.
+///
+/// See `SimplexNoise2D` for details. Like `SimplexNoise3D`, the noise jumps by up to 0.005
+/// between simplices.
+pub struct SimplexNoise4D<H: LatticeHash> {
+    hash: H,
+}
+
+impl<H: LatticeHash> SimplexNoise4D<H> {
+    pub fn new(hash: H) -> SimplexNoise4D<H> {
+        SimplexNoise4D{
+            hash
+        }
+    }
+
+    fn corner(&self, (i, j, k, l): (i64, i64, i64, i64), (x, y, z, w): (f64, f64, f64, f64)) -> f64 {
+        let t = 0.6 - x * x - y * y - z * z - w * w;
+        if t < 0.0 {
+            0.0
+        } else {
+            let (gx, gy, gz, gw) = GRAD4[select_hash(self.hash.hash4(i, j, k, l), 32)];
+            t.powi(4) * (gx * x + gy * y + gz * z + gw * w)
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F, F, F)> for SimplexNoise4D<H> {
+    type Out = F;
+
+    fn value(&self, (x, y, z, w): (F, F, F, F)) -> F {
+        cast(self.evaluate((to_f64(x), to_f64(y), to_f64(z), to_f64(w))))
+    }
+}
+
+impl<H: LatticeHash> SimplexNoise4D<H> {
+    fn evaluate(&self, (x, y, z, w): (f64, f64, f64, f64)) -> f64 {
+        // skew the input space to find the simplex cell
+        let s = (x + y + z + w) * F4;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let k = (z + s).floor();
+        let l = (w + s).floor();
+
+        // unskew the cell origin back to (x, y, z, w) space
+        let t = (i + j + k + l) * G4;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+        let w0 = w - (l - t);
+
+        // rank the coordinates by magnitude, the simplex is traversed from the largest to the
+        // smallest coordinate
+        let mut rank = [0, 0, 0, 0];
+        let coords = [x0, y0, z0, w0];
+        for a in 0..4 {
+            for b in (a + 1)..4 {
+                if coords[a] > coords[b] {
+                    rank[a] += 1;
+                } else {
+                    rank[b] += 1;
+                }
+            }
+        }
+        let step = |threshold: i32| {
+            (if rank[0] >= threshold { 1 } else { 0 },
+             if rank[1] >= threshold { 1 } else { 0 },
+             if rank[2] >= threshold { 1 } else { 0 },
+             if rank[3] >= threshold { 1 } else { 0 })
+        };
+        let (i1, j1, k1, l1) = step(3);
+        let (i2, j2, k2, l2) = step(2);
+        let (i3, j3, k3, l3) = step(1);
+
+        let offset = |o: (i64, i64, i64, i64), factor: f64| {
+            let (oi, oj, ok, ol) = o;
+            (x0 - (oi as f64) + factor * G4,
+             y0 - (oj as f64) + factor * G4,
+             z0 - (ok as f64) + factor * G4,
+             w0 - (ol as f64) + factor * G4)
+        };
+
+        let i = floor_to_i64(i);
+        let j = floor_to_i64(j);
+        let k = floor_to_i64(k);
+        let l = floor_to_i64(l);
+        let add = |(oi, oj, ok, ol): (i64, i64, i64, i64)| {
+            (i.wrapping_add(oi), j.wrapping_add(oj), k.wrapping_add(ok), l.wrapping_add(ol))
+        };
+        let n0 = self.corner((i, j, k, l), (x0, y0, z0, w0));
+        let n1 = self.corner(add((i1, j1, k1, l1)), offset((i1, j1, k1, l1), 1.0));
+        let n2 = self.corner(add((i2, j2, k2, l2)), offset((i2, j2, k2, l2), 2.0));
+        let n3 = self.corner(add((i3, j3, k3, l3)), offset((i3, j3, k3, l3), 3.0));
+        let n4 = self.corner(add((1, 1, 1, 1)), offset((1, 1, 1, 1), 4.0));
+
+        // scale the result to [-1, 1]
+        27.0 * (n0 + n1 + n2 + n3 + n4)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SimplexNoise2D, SimplexNoise3D, SimplexNoise4D};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::hash::LatticeHash;
+    use crate::noise::Noise;
+
+    // The permutation table of the reference implementation of "Simplex noise demystified".
+    static PERM: [usize; 256] = [
+        151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225,
+        140, 36, 103, 30, 69, 142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148,
+        247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219, 203, 117, 35, 11, 32,
+        57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+        74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122,
+        60, 211, 133, 230, 220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54,
+        65, 25, 63, 161, 1, 216, 80, 73, 209, 76, 132, 187, 208, 89, 18, 169,
+        200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173, 186, 3, 64,
+        52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212,
+        207, 206, 59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213,
+        119, 248, 152, 2, 44, 154, 163, 70, 221, 153, 101, 155, 167, 43, 172, 9,
+        129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232, 178, 185, 112, 104,
+        218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162, 241,
+        81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157,
+        184, 84, 204, 176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93,
+        222, 114, 67, 29, 24, 72, 243, 141, 128, 195, 78, 66, 215, 61, 156, 180,
+    ];
+
+    // Picks the gradients like the reference implementation, the hash is the middle of the range
+    // `select_hash` maps to the gradient index.
+    struct ReferenceHash {
+        count: u64,
+    }
+
+    impl ReferenceHash {
+        fn lookup(&self, coords: &[i64]) -> u32 {
+            let index = coords.iter().rev().fold(0, |h, &c| { PERM[((c & 255) as usize + h) & 255] });
+            ((((index as u64 % self.count) * 2 + 1) << 32) / (self.count * 2)) as u32
+        }
+    }
+
+    impl LatticeHash for ReferenceHash {
+        fn hash1(&self, x: i64) -> u32 {
+            self.lookup(&[x])
+        }
+
+        fn hash2(&self, x: i64, y: i64) -> u32 {
+            self.lookup(&[x, y])
+        }
+
+        fn hash3(&self, x: i64, y: i64, z: i64) -> u32 {
+            self.lookup(&[x, y, z])
+        }
+
+        fn hash4(&self, x: i64, y: i64, z: i64, w: i64) -> u32 {
+            self.lookup(&[x, y, z, w])
+        }
+    }
+
+    #[test]
+    fn simplex_noise_reference_test() {
+        let noise_2d = SimplexNoise2D::new(ReferenceHash{ count: 12 });
+        let noise_3d = SimplexNoise3D::new(ReferenceHash{ count: 12 });
+        let noise_4d = SimplexNoise4D::new(ReferenceHash{ count: 32 });
+        let close = |value: f64, expected: f64| { (value - expected).abs() < 1e-12 };
+        assert!(close(noise_2d.value((0.3, 0.7)), 0.2552206334201348));
+        assert!(close(noise_2d.value((-12.25, 4.5)), -0.6871512954212754));
+        assert!(close(noise_2d.value((101.9, -33.3)), -0.37274590144374997));
+        assert!(close(noise_3d.value((0.3, 0.7, -0.2)), -0.32361265804115225));
+        assert!(close(noise_3d.value((-12.25, 4.5, 8.125)), -0.45325942242940065));
+        assert!(close(noise_3d.value((101.9, -33.3, 0.01)), 0.6125445230252203));
+        assert!(close(noise_4d.value((0.3, 0.7, -0.2, 0.9)), -0.10626476717589214));
+        assert!(close(noise_4d.value((-12.25, 4.5, 8.125, -3.75)), -0.17915530449204267));
+        assert!(close(noise_4d.value((101.9, -33.3, 0.01, 57.6)), -0.11116600091118702));
+    }
+
+    #[test]
+    fn simplex_noise_continuity_test() {
+        let noise_2d = SimplexNoise2D::new(DefaultI32Noise::new(5));
+        let noise_3d = SimplexNoise3D::new(DefaultI32Noise::new(5));
+        let noise_4d = SimplexNoise4D::new(DefaultI32Noise::new(5));
+        let (mut min, mut max) = (0.0f64, 0.0f64);
+        for i in 0..2000 {
+            let (x, y, z, w) = (i as f64 * 0.137, i as f64 * -0.291, i as f64 * 0.053, i as f64 * 0.419);
+            let d = 1e-6;
+            let values = [
+                (noise_2d.value((x, y)), noise_2d.value((x + d, y)), noise_2d.value((x, y + d))),
+                (noise_3d.value((x, y, z)), noise_3d.value((x + d, y, z)), noise_3d.value((x, y, z + d))),
+                (noise_4d.value((x, y, z, w)), noise_4d.value((x, y + d, z, w)), noise_4d.value((x, y, z, w + d))),
+            ];
+            // the slope of the noise is bounded, in 3D and 4D the corners reach a little beyond
+            // their simplices and the noise jumps slightly when a step crosses into the next one
+            for (&(value, a, b), jump) in values.iter().zip([0.0, 0.005, 0.005]) {
+                assert!((a - value).abs() < 100.0 * d + jump);
+                assert!((b - value).abs() < 100.0 * d + jump);
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+        // the gradients are not degenerate, the noise reaches most of its range
+        assert!(min < -0.5 && max > 0.5);
+    }
+
+    #[test]
+    fn simplex_noise_2d_test() {
+        let noise = SimplexNoise2D::new(DefaultI32Noise::new(0));
+        for i in -100..100 {
+            for j in -100..100 {
+                let value = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn simplex_noise_3d_test() {
+        let noise = SimplexNoise3D::new(DefaultI32Noise::new(0));
+        for i in -20..20 {
+            for j in -20..20 {
+                for k in -20..20 {
+                    let value = noise.value((i as f64 / 7.0, j as f64 / 7.0, k as f64 / 7.0));
+                    assert!((-1.0..=1.0).contains(&value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simplex_noise_4d_test() {
+        let noise = SimplexNoise4D::new(DefaultI32Noise::new(0));
+        for i in -8..8 {
+            for j in -8..8 {
+                for k in -8..8 {
+                    for l in -8..8 {
+                        let value = noise.value((i as f64 / 3.0, j as f64 / 3.0, k as f64 / 3.0, l as f64 / 3.0));
+                        assert!((-1.0..=1.0).contains(&value));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::interpolate::{Interpolator, sawtooth};
+use crate::hash::LatticeHash;
+use crate::lattice::unit;
+use crate::float::{cast, floor_to_i64};
+use crate::noise::Noise;
+use num_traits::Float;
+
+/// Produces two-dimensional value-interpolated noise using a lattice hash.
+///
+/// Every lattice point gets a value between -1 and 1 from the hash, the values in between are
+/// interpolated. This results in more blocky noise than the gradient noise of
+/// `InterpolatedNoise2D`.
+pub struct ValueNoise2D<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        ValueNoise2D<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> ValueNoise2D<H, I> {
+        ValueNoise2D{
+            hash,
+            interpolator
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<(F, F)>
+        for ValueNoise2D<H, I> {
+
+    type Out = F;
+
+    fn value(&self, (pos_x, pos_y): (F, F)) -> F {
+        let x0 = floor_to_i64(pos_x);
+        let x1 = x0.wrapping_add(1);
+        let y0 = floor_to_i64(pos_y);
+        let y1 = y0.wrapping_add(1);
+
+        let px = sawtooth(pos_x);
+        let py = sawtooth(pos_y);
+
+        // nXY for X,Y <- {0,1}
+        let n00: F = cast(unit(self.hash.hash2(x0, y0)));
+        let n01: F = cast(unit(self.hash.hash2(x0, y1)));
+        let n10: F = cast(unit(self.hash.hash2(x1, y0)));
+        let n11: F = cast(unit(self.hash.hash2(x1, y1)));
+
+        // interpolate both x directions
+        let val1 = self.interpolator.interpolate(n00, n10, px);
+        let val2 = self.interpolator.interpolate(n01, n11, px);
+
+        // interpolate in y direction
+        self.interpolator.interpolate(val1, val2, py)
+    }
+}
+
+/// Produces three-dimensional value-interpolated noise using a lattice hash.
+///
+/// See `ValueNoise2D` for details.
+pub struct ValueNoise3D<H: LatticeHash, I> {
+    hash: H,
+    interpolator: I,
+}
+
+impl<H: LatticeHash, I>
+        ValueNoise3D<H, I> {
+
+    pub fn new(hash: H, interpolator: I) -> ValueNoise3D<H, I> {
+        ValueNoise3D{
+            hash,
+            interpolator
+        }
+    }
+}
+
+impl<F: Float, H: LatticeHash, I: Interpolator<F>>
+        Noise<(F, F, F)>
+        for ValueNoise3D<H, I> {
+
+    type Out = F;
+
+    fn value(&self, (pos_x, pos_y, pos_z): (F, F, F)) -> F {
+        let x0 = floor_to_i64(pos_x);
+        let x1 = x0.wrapping_add(1);
+        let y0 = floor_to_i64(pos_y);
+        let y1 = y0.wrapping_add(1);
+        let z0 = floor_to_i64(pos_z);
+        let z1 = z0.wrapping_add(1);
+
+        let px = sawtooth(pos_x);
+        let py = sawtooth(pos_y);
+        let pz = sawtooth(pos_z);
+
+        // nXYZ for X,Y,Z <- {0,1}
+        let n000: F = cast(unit(self.hash.hash3(x0, y0, z0)));
+        let n001: F = cast(unit(self.hash.hash3(x0, y0, z1)));
+        let n010: F = cast(unit(self.hash.hash3(x0, y1, z0)));
+        let n011: F = cast(unit(self.hash.hash3(x0, y1, z1)));
+        let n100: F = cast(unit(self.hash.hash3(x1, y0, z0)));
+        let n101: F = cast(unit(self.hash.hash3(x1, y0, z1)));
+        let n110: F = cast(unit(self.hash.hash3(x1, y1, z0)));
+        let n111: F = cast(unit(self.hash.hash3(x1, y1, z1)));
+
+        // interpolate all four x directions
+        let val00 = self.interpolator.interpolate(n000, n100, px);
+        let val01 = self.interpolator.interpolate(n001, n101, px);
+        let val10 = self.interpolator.interpolate(n010, n110, px);
+        let val11 = self.interpolator.interpolate(n011, n111, px);
+
+        // interpolate both y directions
+        let val0 = self.interpolator.interpolate(val00, val10, py);
+        let val1 = self.interpolator.interpolate(val01, val11, py);
+
+        // interpolate in z direction
+        self.interpolator.interpolate(val0, val1, pz)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ValueNoise2D, ValueNoise3D};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::interpolate::LinearInterpolator;
+    use crate::lattice::{index_2d, index_3d};
+    use crate::noise::Noise;
+
+    #[test]
+    fn value_noise_2d_test() {
+        let source = DefaultI32Noise::new(0);
+        let noise = ValueNoise2D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -100..100 {
+            for j in -100..100 {
+                let value = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                assert!((-1.0..=1.0).contains(&value));
+            }
+        }
+        for i in -10..10 {
+            for j in -10..10 {
+                let value = noise.value((i as f64, j as f64));
+                assert!((value - source.value(index_2d(i, j))).abs() < 0.0001);
+            }
+        }
+    }
+
+    #[test]
+    fn value_noise_3d_test() {
+        let source = DefaultI32Noise::new(0);
+        let noise = ValueNoise3D::new(DefaultI32Noise::new(0), LinearInterpolator);
+        for i in -10..10 {
+            for j in -10..10 {
+                for k in -10..10 {
+                    let value = noise.value((i as f64 / 3.0, j as f64 / 3.0, k as f64 / 3.0));
+                    assert!((-1.0..=1.0).contains(&value));
+                    if i % 3 == 0 && j % 3 == 0 && k % 3 == 0 {
+                        let expected = source.value(index_3d(i / 3, j / 3, k / 3));
+                        assert!((value - expected).abs() < 0.0001);
+                    }
+                }
+            }
+        }
+    }
+}
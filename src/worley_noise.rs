@@ -0,0 +1,408 @@
+use crate::noise::Noise;
+use crate::hash::LatticeHash;
+use crate::lattice::unit;
+use crate::float::{cast, to_f64, floor_to_i64};
+use num_traits::Float;
+
+// The last hash coordinate selects the property of a feature point.
+static X_PROPERTY: i64 = 0;
+static Y_PROPERTY: i64 = 1;
+static Z_PROPERTY: i64 = 2;
+static VALUE_PROPERTY: i64 = 3;
+
+// The number of rings searched at most, see `max_ring`.
+static MAX_RING: i64 = 64;
+
+/// The function used to measure the distance between a position and a feature point.
+#[derive(Clone, Copy)]
+pub enum WorleyDistance {
+    /// The length of the straight line, results in round cells.
+    Euclidean,
+    /// The sum of the distances along each axis, results in diamond shaped cells.
+    Manhattan,
+    /// The largest distance along any axis, results in square cells.
+    Chebyshev,
+    /// The generalization of the above, `Minkowski(1.0)` is the Manhattan distance and
+    /// `Minkowski(2.0)` is the Euclidean distance. The exponent has to be positive.
+    Minkowski(f64),
+}
+
+impl WorleyDistance {
+    fn distance(&self, delta: &[f64]) -> f64 {
+        match *self {
+            WorleyDistance::Euclidean =>
+                delta.iter().fold(0.0, |acc, d| acc + d * d).sqrt(),
+            WorleyDistance::Manhattan =>
+                delta.iter().fold(0.0, |acc, d| acc + d.abs()),
+            WorleyDistance::Chebyshev =>
+                delta.iter().fold(0.0, |acc, d| acc.max(d.abs())),
+            WorleyDistance::Minkowski(p) => {
+                // scaled by the largest component, so that large exponents do not overflow
+                let max = delta.iter().fold(0.0, |acc: f64, d| acc.max(d.abs()));
+                if max == 0.0 {
+                    0.0
+                } else {
+                    max * delta.iter().fold(0.0, |acc, d| acc + (d.abs() / max).powf(p)).powf(1.0 / p)
+                }
+            },
+        }
+    }
+}
+
+/// The scalar values that can be taken from a `WorleyCell`.
+#[derive(Clone, Copy)]
+pub enum WorleyReturn {
+    /// The distance to the nearest feature point.
+    F1,
+    /// The distance to the second nearest feature point.
+    F2,
+    /// The difference of `F2` and `F1`, this is zero along the borders of the cells.
+    F2MinusF1,
+    /// The value of the cell of the nearest feature point, between -1 and 1.
+    CellValue,
+}
+
+/// The result of a cellular noise lookup.
+#[derive(Clone, Copy)]
+pub struct WorleyCell<F, P> {
+    /// The distance to the nearest feature point.
+    pub f1: F,
+    /// The distance to the second nearest feature point.
+    pub f2: F,
+    /// A value between -1 and 1 that is the same for all positions nearest to the same
+    /// feature point.
+    pub value: F,
+    /// The position of the nearest feature point.
+    pub point: P,
+}
+
+impl<F: Float, P> WorleyCell<F, P> {
+    /// Returns the requested scalar value of this cell.
+    pub fn get(&self, ret: WorleyReturn) -> F {
+        match ret {
+            WorleyReturn::F1 => self.f1,
+            WorleyReturn::F2 => self.f2,
+            WorleyReturn::F2MinusF1 => self.f2 - self.f1,
+            WorleyReturn::CellValue => self.value,
+        }
+    }
+}
+
+impl<P> WorleyCell<f64, P> {
+    fn empty(point: P) -> WorleyCell<f64, P> {
+        WorleyCell{ f1: Float::infinity(), f2: Float::infinity(), value: 0.0, point }
+    }
+
+    fn add(&mut self, dist: f64, value: f64, point: P) {
+        if dist < self.f1 {
+            self.f2 = self.f1;
+            self.f1 = dist;
+            self.value = value;
+            self.point = point;
+        } else if dist < self.f2 {
+            self.f2 = dist;
+        }
+    }
+
+    fn cast<F: Float, Q>(self, point: impl Fn(P) -> Q) -> WorleyCell<F, Q> {
+        WorleyCell{ f1: cast(self.f1), f2: cast(self.f2), value: cast(self.value), point: point(self.point) }
+    }
+}
+
+// The cells are searched in rings of growing Chebyshev distance around the cell of the position.
+// Every feature point in ring `r` is at least `r - 1` plus the distance to the nearest border of
+// the own cell away along one axis, and no distance function is shorter than that. Once F2 is
+// not longer the remaining rings cannot change the result.
+fn ring_distance(ring: i64, fractions: &[f64]) -> f64 {
+    let border = fractions.iter().fold(0.5, |acc: f64, f| acc.min(f.min(1.0 - f)));
+    (ring - 1) as f64 + border.max(0.0)
+}
+
+// Returns the last ring that can contain one of the two nearest feature points. Both are at most
+// two cells away along every axis, as the cell of the position and its neighbours contain
+// enough points. Tiny Minkowski exponents make even the neighbours extremely far away, for them
+// the search stops at `MAX_RING`.
+fn max_ring(distance: WorleyDistance, dims: usize) -> i64 {
+    if let WorleyDistance::Minkowski(p) = distance {
+        assert!(p > 0.0, "the exponent of the Minkowski distance must be positive");
+    }
+    let bound = distance.distance(&[2.0; 3][..dims]);
+    (bound.ceil() + 1.0).min(MAX_RING as f64) as i64
+}
+
+// Beyond the range of `i64` the lattice coordinates saturate and the neighbouring cells wrap
+// around to the other end of the lattice, there only the direct neighbours are searched.
+fn saturated(lattice: &[i64]) -> bool {
+    lattice.iter().any(|l| *l == i64::MIN || *l == i64::MAX)
+}
+
+/// Produces two-dimensional cellular (Worley) noise using a lattice hash.
+///
+/// Each square of the integer lattice contains one feature point at a position chosen by the
+/// hash. The result is a `WorleyCell` describing the nearest feature points, use
+/// `WorleyCell::get` (e.g. with an `OutputOp`) to turn it into scalar noise. A position with
+/// a coordinate that is not finite has no nearest feature point, both distances are infinite.
+pub struct WorleyNoise2D<H: LatticeHash> {
+    hash: H,
+    distance: WorleyDistance,
+    max_ring: i64,
+}
+
+impl<H: LatticeHash> WorleyNoise2D<H> {
+    /// Panics if the exponent of a Minkowski distance is not positive.
+    pub fn new(hash: H, distance: WorleyDistance) -> WorleyNoise2D<H> {
+        WorleyNoise2D{
+            hash,
+            distance,
+            max_ring: max_ring(distance, 2)
+        }
+    }
+
+    fn feature_point(&self, x: i64, y: i64) -> ((f64, f64), f64) {
+        let px = (x as f64) + (unit(self.hash.hash3(x, y, X_PROPERTY)) + 1.0) / 2.0;
+        let py = (y as f64) + (unit(self.hash.hash3(x, y, Y_PROPERTY)) + 1.0) / 2.0;
+        ((px, py), unit(self.hash.hash3(x, y, VALUE_PROPERTY)))
+    }
+
+    fn evaluate(&self, (pos_x, pos_y): (f64, f64)) -> WorleyCell<f64, (f64, f64)> {
+        if !(pos_x.is_finite() && pos_y.is_finite()) {
+            return WorleyCell::empty((pos_x, pos_y));
+        }
+        let x0 = floor_to_i64(pos_x);
+        let y0 = floor_to_i64(pos_y);
+        let fractions = [pos_x - x0 as f64, pos_y - y0 as f64];
+        let max_ring = if saturated(&[x0, y0]) { 1 } else { self.max_ring };
+
+        let mut cell = WorleyCell::empty((0.0, 0.0));
+        let mut ring = 0;
+        while ring <= max_ring && cell.f2 > ring_distance(ring, &fractions) {
+            // the neighbours are visited by offset, the lattice coordinates wrap at the end of
+            // the `i64` range
+            for dx in -ring..=ring {
+                for dy in -ring..=ring {
+                    if dx.abs().max(dy.abs()) != ring {
+                        continue;
+                    }
+                    let ((px, py), value) = self.feature_point(x0.wrapping_add(dx), y0.wrapping_add(dy));
+                    cell.add(self.distance.distance(&[px - pos_x, py - pos_y]), value, (px, py));
+                }
+            }
+            ring += 1;
+        }
+        cell
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F)> for WorleyNoise2D<H> {
+    type Out = WorleyCell<F, (F, F)>;
+
+    fn value(&self, (x, y): (F, F)) -> WorleyCell<F, (F, F)> {
+        self.evaluate((to_f64(x), to_f64(y))).cast(|(px, py)| (cast(px), cast(py)))
+    }
+}
+
+/// Produces three-dimensional cellular (Worley) noise using a lattice hash.
+///
+/// See `WorleyNoise2D` for details.
+pub struct WorleyNoise3D<H: LatticeHash> {
+    hash: H,
+    distance: WorleyDistance,
+    max_ring: i64,
+}
+
+impl<H: LatticeHash> WorleyNoise3D<H> {
+    /// Panics if the exponent of a Minkowski distance is not positive.
+    pub fn new(hash: H, distance: WorleyDistance) -> WorleyNoise3D<H> {
+        WorleyNoise3D{
+            hash,
+            distance,
+            max_ring: max_ring(distance, 3)
+        }
+    }
+
+    fn feature_point(&self, x: i64, y: i64, z: i64) -> ((f64, f64, f64), f64) {
+        let px = (x as f64) + (unit(self.hash.hash4(x, y, z, X_PROPERTY)) + 1.0) / 2.0;
+        let py = (y as f64) + (unit(self.hash.hash4(x, y, z, Y_PROPERTY)) + 1.0) / 2.0;
+        let pz = (z as f64) + (unit(self.hash.hash4(x, y, z, Z_PROPERTY)) + 1.0) / 2.0;
+        ((px, py, pz), unit(self.hash.hash4(x, y, z, VALUE_PROPERTY)))
+    }
+
+    fn evaluate(&self, (pos_x, pos_y, pos_z): (f64, f64, f64)) -> WorleyCell<f64, (f64, f64, f64)> {
+        if !(pos_x.is_finite() && pos_y.is_finite() && pos_z.is_finite()) {
+            return WorleyCell::empty((pos_x, pos_y, pos_z));
+        }
+        let x0 = floor_to_i64(pos_x);
+        let y0 = floor_to_i64(pos_y);
+        let z0 = floor_to_i64(pos_z);
+        let fractions = [pos_x - x0 as f64, pos_y - y0 as f64, pos_z - z0 as f64];
+        let max_ring = if saturated(&[x0, y0, z0]) { 1 } else { self.max_ring };
+
+        let mut cell = WorleyCell::empty((0.0, 0.0, 0.0));
+        let mut ring = 0;
+        while ring <= max_ring && cell.f2 > ring_distance(ring, &fractions) {
+            for dx in -ring..=ring {
+                for dy in -ring..=ring {
+                    for dz in -ring..=ring {
+                        if dx.abs().max(dy.abs()).max(dz.abs()) != ring {
+                            continue;
+                        }
+                        let ((px, py, pz), value) = self.feature_point(x0.wrapping_add(dx), y0.wrapping_add(dy),
+                                                                       z0.wrapping_add(dz));
+                        let dist = self.distance.distance(&[px - pos_x, py - pos_y, pz - pos_z]);
+                        cell.add(dist, value, (px, py, pz));
+                    }
+                }
+            }
+            ring += 1;
+        }
+        cell
+    }
+}
+
+impl<F: Float, H: LatticeHash> Noise<(F, F, F)> for WorleyNoise3D<H> {
+    type Out = WorleyCell<F, (F, F, F)>;
+
+    fn value(&self, (x, y, z): (F, F, F)) -> WorleyCell<F, (F, F, F)> {
+        self.evaluate((to_f64(x), to_f64(y), to_f64(z))).cast(|(px, py, pz)| (cast(px), cast(py), cast(pz)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{WorleyCell, WorleyNoise2D, WorleyNoise3D, WorleyDistance, WorleyReturn};
+    use crate::default_noise::DefaultI32Noise;
+    use crate::hash::{LatticeHash, PcgHash};
+    use crate::lattice::unit;
+    use crate::noise::Noise;
+
+    #[test]
+    fn worley_noise_2d_test() {
+        let distances = [WorleyDistance::Euclidean, WorleyDistance::Manhattan,
+                         WorleyDistance::Chebyshev, WorleyDistance::Minkowski(3.0)];
+        for distance in distances.iter() {
+            let noise = WorleyNoise2D::new(DefaultI32Noise::new(0), *distance);
+            for i in -50..50 {
+                for j in -50..50 {
+                    let cell = noise.value((i as f64 / 7.0, j as f64 / 7.0));
+                    assert!(0.0 <= cell.f1 && cell.f1 <= cell.f2);
+                    assert!(cell.get(WorleyReturn::F2MinusF1) >= 0.0);
+                    assert!(-1.0 <= cell.value && cell.value <= 1.0);
+
+                    // the nearest feature point belongs to its own cell
+                    let nearest = noise.value(cell.point);
+                    assert!(nearest.f1 < 1e-9);
+                    assert!(nearest.value == cell.value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn worley_noise_3d_test() {
+        let noise = WorleyNoise3D::new(DefaultI32Noise::new(0), WorleyDistance::Euclidean);
+        for i in -10..10 {
+            for j in -10..10 {
+                for k in -10..10 {
+                    let cell = noise.value((i as f64 / 7.0, j as f64 / 7.0, k as f64 / 7.0));
+                    assert!(0.0 <= cell.f1 && cell.f1 <= cell.f2);
+                    assert!(-1.0 <= cell.get(WorleyReturn::CellValue) && cell.value <= 1.0);
+
+                    let nearest = noise.value(cell.point);
+                    assert!(nearest.f1 < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn worley_noise_brute_force_test() {
+        let distances = [WorleyDistance::Euclidean, WorleyDistance::Manhattan, WorleyDistance::Chebyshev,
+                         WorleyDistance::Minkowski(0.5), WorleyDistance::Minkowski(3.0)];
+        let random = PcgHash::new(1);
+        let coordinate = |i: i64| { unit(random.hash1(i)) * 50.0 };
+        for distance in distances.iter() {
+            // the second nearest point is at most as far away as a point two cells away along
+            // every axis
+            let radius = distance.distance(&[2.0, 2.0, 2.0]).ceil() as i64 + 1;
+
+            let noise = WorleyNoise2D::new(DefaultI32Noise::new(0), *distance);
+            for i in 0..500 {
+                let (x, y) = (coordinate(2 * i), coordinate(2 * i + 1));
+                let mut expected = WorleyCell::empty((0.0, 0.0));
+                for cx in (x.floor() as i64 - radius)..=(x.floor() as i64 + radius) {
+                    for cy in (y.floor() as i64 - radius)..=(y.floor() as i64 + radius) {
+                        let ((px, py), value) = noise.feature_point(cx, cy);
+                        expected.add(distance.distance(&[px - x, py - y]), value, (px, py));
+                    }
+                }
+                let cell = noise.value((x, y));
+                assert!(cell.f1 == expected.f1 && cell.f2 == expected.f2 && cell.value == expected.value);
+            }
+
+            let noise = WorleyNoise3D::new(DefaultI32Noise::new(0), *distance);
+            for i in 0..20 {
+                let (x, y, z) = (coordinate(3 * i), coordinate(3 * i + 1), coordinate(3 * i + 2));
+                let mut expected = WorleyCell::empty((0.0, 0.0, 0.0));
+                for cx in (x.floor() as i64 - radius)..=(x.floor() as i64 + radius) {
+                    for cy in (y.floor() as i64 - radius)..=(y.floor() as i64 + radius) {
+                        for cz in (z.floor() as i64 - radius)..=(z.floor() as i64 + radius) {
+                            let ((px, py, pz), value) = noise.feature_point(cx, cy, cz);
+                            expected.add(distance.distance(&[px - x, py - y, pz - z]), value, (px, py, pz));
+                        }
+                    }
+                }
+                let cell = noise.value((x, y, z));
+                assert!(cell.f1 == expected.f1 && cell.f2 == expected.f2 && cell.value == expected.value);
+            }
+        }
+    }
+
+    #[test]
+    fn worley_noise_f32_test() {
+        let noise = WorleyNoise2D::new(DefaultI32Noise::new(0), WorleyDistance::Euclidean);
+        for i in -50..50 {
+            let cell = noise.value((i as f32 / 7.0, i as f32 / 3.0));
+            let expected = noise.value((i as f32 as f64 / 7.0, i as f32 as f64 / 3.0));
+            assert!((cell.f1 as f64 - expected.f1).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn worley_noise_extreme_test() {
+        let noise_2d = WorleyNoise2D::new(DefaultI32Noise::new(0), WorleyDistance::Euclidean);
+        let noise_3d = WorleyNoise3D::new(DefaultI32Noise::new(0), WorleyDistance::Euclidean);
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY].iter() {
+            let cell = noise_2d.value((*x, 0.5));
+            assert!(cell.f1 == f64::INFINITY && cell.f2 == f64::INFINITY && cell.value == 0.0);
+            let cell = noise_3d.value((0.5, *x, 0.5));
+            assert!(cell.f1 == f64::INFINITY && cell.f2 == f64::INFINITY && cell.value == 0.0);
+        }
+        for x in [1e20f64, -1e20, 9.3e18].iter() {
+            let cell = noise_2d.value((*x, 0.5));
+            assert!(cell.f1.is_finite() && cell.f1 <= cell.f2);
+            let cell = noise_3d.value((0.5, 0.5, *x));
+            assert!(cell.f1.is_finite() && cell.f1 <= cell.f2);
+        }
+
+        // huge and tiny exponents
+        for p in [1e-3, 0.1, 1e3, f64::INFINITY].iter() {
+            let noise = WorleyNoise2D::new(DefaultI32Noise::new(0), WorleyDistance::Minkowski(*p));
+            assert!(noise.value((0.3, 0.7)).f1 <= noise.value((0.3, 0.7)).f2);
+        }
+        let chebyshev = WorleyNoise3D::new(DefaultI32Noise::new(0), WorleyDistance::Chebyshev);
+        let minkowski = WorleyNoise3D::new(DefaultI32Noise::new(0), WorleyDistance::Minkowski(f64::INFINITY));
+        assert!(chebyshev.value((0.3, 0.7, 1.1)).f2 == minkowski.value((0.3, 0.7, 1.1)).f2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn worley_noise_minkowski_zero_test() {
+        WorleyNoise2D::new(DefaultI32Noise::new(0), WorleyDistance::Minkowski(0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn worley_noise_minkowski_nan_test() {
+        WorleyNoise3D::new(DefaultI32Noise::new(0), WorleyDistance::Minkowski(f64::NAN));
+    }
+}